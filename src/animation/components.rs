@@ -0,0 +1,205 @@
+use bevy::{platform::collections::HashMap, prelude::*};
+
+use crate::characters::assets::SHEET_COLUMNS;
+
+/// Estados de animación de un personaje (player o enemigo).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AnimationState {
+    Idle,
+    Walk,
+    Jump,
+    Fall,
+    WallSlide,
+    LedgeHang,
+    Hurt,
+    Throw,
+    Death,
+}
+
+impl AnimationState {
+    // Estos clips se reproducen enteros antes de volver al estado de movimiento
+    fn is_one_shot(self) -> bool {
+        matches!(self, AnimationState::Hurt | AnimationState::Throw)
+    }
+}
+
+/// Un clip es un rango de frames de la hoja completa del personaje (`*_full.png`).
+///
+/// Los frames miran a la derecha; si la hoja tiene la misma animación dibujada hacia la
+/// izquierda, `left_first` es su primer frame, y si no, se voltea el sprite.
+#[derive(Clone)]
+pub struct AnimationClip {
+    pub first: usize,
+    pub last: usize,
+    pub left_first: Option<usize>,
+    pub fps: u8,
+    pub looping: bool,
+}
+
+impl AnimationClip {
+    pub fn looping(first: usize, last: usize, fps: u8) -> Self {
+        Self {
+            first,
+            last,
+            left_first: None,
+            fps,
+            looping: true,
+        }
+    }
+
+    pub fn once(first: usize, last: usize, fps: u8) -> Self {
+        Self {
+            looping: false,
+            ..Self::looping(first, last, fps)
+        }
+    }
+
+    /// Frame de la hoja para el frame actual del clip y el lado al que mira, y si hay
+    /// que voltear el sprite.
+    pub fn atlas_index(&self, frame: usize, facing_left: bool) -> (usize, bool) {
+        match (self.left_first, facing_left) {
+            (Some(left_first), true) => (left_first + frame - self.first, false),
+            _ => (frame, facing_left),
+        }
+    }
+}
+
+/// Máquina de estados de animación de un personaje. Va en la entidad del personaje y
+/// pinta en su hijo `CharacterSprite`.
+///
+/// Los sistemas de juego eligen el estado de movimiento con `set_state` cada frame y
+/// lanzan los clips de una sola vez (daño, lanzar) con `play_once`. `Death` tiene
+/// prioridad sobre todo lo demás.
+#[derive(Component)]
+pub struct CharacterAnimator {
+    clips: HashMap<AnimationState, AnimationClip>,
+    state: AnimationState,
+    pub facing: f32,
+    pub frame: usize,
+    frame_timer: Timer,
+    finished: bool,
+}
+
+impl CharacterAnimator {
+    pub fn new(clips: HashMap<AnimationState, AnimationClip>) -> Self {
+        let mut animator = Self {
+            clips,
+            state: AnimationState::Idle,
+            facing: 1.0,
+            frame: 0,
+            frame_timer: Timer::default(),
+            finished: false,
+        };
+        animator.restart();
+        animator
+    }
+
+    /// Cambia al estado de movimiento, salvo que haya un clip de una sola vez sin terminar.
+    pub fn set_state(&mut self, state: AnimationState) {
+        let locked = self.state.is_one_shot() && !self.finished && state != AnimationState::Death;
+        if state == self.state || locked {
+            return;
+        }
+        self.state = state;
+        self.restart();
+    }
+
+    /// Reproduce un clip entero desde el principio (p. ej. Hurt o Throw).
+    pub fn play_once(&mut self, state: AnimationState) {
+        if self.state == AnimationState::Death {
+            return;
+        }
+        self.state = state;
+        self.restart();
+    }
+
+    /// Clip del estado actual; si el personaje no tiene ese clip, usa el de Idle.
+    pub fn clip(&self) -> Option<&AnimationClip> {
+        self.clips
+            .get(&self.state)
+            .or_else(|| self.clips.get(&AnimationState::Idle))
+    }
+
+    pub fn tick(&mut self, delta: std::time::Duration) {
+        let Some(clip) = self.clip() else {
+            return;
+        };
+        let (first, last, looping) = (clip.first, clip.last, clip.looping);
+
+        self.frame_timer.tick(delta);
+        for _ in 0..self.frame_timer.times_finished_this_tick() {
+            if self.frame < last {
+                self.frame += 1;
+            } else if looping {
+                self.frame = first;
+            } else {
+                self.finished = true;
+            }
+        }
+    }
+
+    fn restart(&mut self) {
+        let (first, fps) = self.clip().map_or((0, 10), |clip| (clip.first, clip.fps));
+        self.frame = first;
+        self.finished = false;
+        self.frame_timer = Timer::from_seconds(1.0 / fps.max(1) as f32, TimerMode::Repeating);
+    }
+}
+
+// Marca el sprite hijo que dibuja la animación del personaje
+#[derive(Component)]
+pub struct CharacterSprite;
+
+// Primer frame de cada fila de las hojas completas: de pie, andando a la izquierda y
+// andando a la derecha
+const IDLE_ROW: usize = 0;
+const WALK_LEFT_ROW: usize = SHEET_COLUMNS as usize;
+const WALK_RIGHT_ROW: usize = 2 * SHEET_COLUMNS as usize;
+
+impl CharacterAnimator {
+    /// Clips de un gato a partir de las filas de su hoja completa.
+    ///
+    /// Las hojas solo tienen filas de pie y andando, así que salto, caída, pared, daño,
+    /// lanzar y muerte usan las poses de esas filas que más se parecen.
+    pub fn for_cat() -> Self {
+        // Pasa las columnas de un clip a las filas de andar, una para cada lado
+        let walking = |clip: AnimationClip| AnimationClip {
+            first: WALK_RIGHT_ROW + clip.first,
+            last: WALK_RIGHT_ROW + clip.last,
+            left_first: Some(WALK_LEFT_ROW + clip.first),
+            ..clip
+        };
+        Self::new(HashMap::from_iter([
+            (
+                AnimationState::Idle,
+                AnimationClip::looping(IDLE_ROW, IDLE_ROW + 7, 10),
+            ),
+            (
+                AnimationState::Walk,
+                walking(AnimationClip::looping(0, 7, 10)),
+            ),
+            (AnimationState::Jump, walking(AnimationClip::once(2, 2, 10))),
+            (AnimationState::Fall, walking(AnimationClip::once(6, 6, 10))),
+            (
+                AnimationState::WallSlide,
+                walking(AnimationClip::looping(0, 1, 4)),
+            ),
+            (
+                AnimationState::LedgeHang,
+                walking(AnimationClip::looping(2, 3, 3)),
+            ),
+            (
+                AnimationState::Hurt,
+                AnimationClip::once(IDLE_ROW + 4, IDLE_ROW + 7, 16),
+            ),
+            (
+                AnimationState::Throw,
+                walking(AnimationClip::once(0, 3, 16)),
+            ),
+            (
+                AnimationState::Death,
+                AnimationClip::once(IDLE_ROW, IDLE_ROW, 10),
+            ),
+        ]))
+    }
+}
@@ -0,0 +1,22 @@
+pub mod components;
+pub mod systems;
+
+use bevy::prelude::*;
+
+use crate::{
+    animation::systems::{animate_characters, update_facing},
+    game_state::GameState,
+};
+
+pub struct CharacterAnimationPlugin;
+
+impl Plugin for CharacterAnimationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (update_facing, animate_characters)
+                .chain()
+                .run_if(in_state(GameState::Game)),
+        );
+    }
+}
@@ -0,0 +1,44 @@
+use bevy::prelude::*;
+
+use crate::{
+    animation::components::{CharacterAnimator, CharacterSprite},
+    physics::Velocity,
+};
+
+// Por debajo de esta velocidad horizontal el personaje no cambia de lado
+const FACING_MIN_SPEED: f32 = 1.0;
+
+// El lado al que mira el personaje sale de su velocidad, no de las teclas
+pub fn update_facing(mut query: Query<(&mut CharacterAnimator, &Velocity)>) {
+    for (mut animator, velocity) in &mut query {
+        if velocity.velocity.x.abs() > FACING_MIN_SPEED {
+            animator.facing = velocity.velocity.x.signum();
+        }
+    }
+}
+
+// Avanza los clips y los pinta en el sprite hijo de cada personaje
+pub fn animate_characters(
+    time: Res<Time>,
+    mut animator_query: Query<(&mut CharacterAnimator, &Children)>,
+    mut sprite_query: Query<&mut Sprite, With<CharacterSprite>>,
+) {
+    for (mut animator, children) in &mut animator_query {
+        animator.tick(time.delta());
+
+        let Some(clip) = animator.clip() else {
+            continue;
+        };
+        let (index, flip_x) = clip.atlas_index(animator.frame, animator.facing < 0.0);
+
+        for child in children.iter() {
+            let Ok(mut sprite) = sprite_query.get_mut(child) else {
+                continue;
+            };
+            sprite.flip_x = flip_x;
+            if let Some(atlas) = &mut sprite.texture_atlas {
+                atlas.index = index;
+            }
+        }
+    }
+}
@@ -0,0 +1,50 @@
+use std::fs;
+
+use bevy::prelude::*;
+
+use crate::characters::components::{CharacterSheet, CharacterSheets, GameCharacters};
+
+const GAME_CHARACTERS_PATH: &str = "assets/game_characters.json";
+pub const SHEET_COLUMNS: u32 = 8;
+// Tamaño al que se dibujan los frames, para que las hojas de 32 px y las de 64 px se vean igual
+pub const CHARACTER_SPRITE_SIZE: f32 = 64.0;
+
+// Carga las estadísticas de todos los personajes, players y enemigos
+pub fn load_game_characters(mut commands: Commands) {
+    let characters = serde_json::from_str(
+        &fs::read_to_string(GAME_CHARACTERS_PATH).expect("Failed to read game characters JSON"),
+    )
+    .expect("Failed to parse game characters JSON");
+
+    commands.insert_resource(GameCharacters(characters));
+}
+
+// Carga la hoja completa de cada personaje con un layout según el tamaño de sus frames
+pub fn load_character_sheets(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    game_characters: Res<GameCharacters>,
+    mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+) {
+    let mut sheets = CharacterSheets::default();
+    for (key, character) in game_characters.0.iter() {
+        let Some(sheet) = &character.sheet else {
+            continue;
+        };
+        sheets.0.insert(
+            key.clone(),
+            CharacterSheet {
+                image: asset_server.load(format!("characters/{}/{sheet}", character.name)),
+                layout: texture_atlas_layouts.add(TextureAtlasLayout::from_grid(
+                    UVec2::new(character.width, character.height),
+                    SHEET_COLUMNS,
+                    character.rows,
+                    None,
+                    None,
+                )),
+            },
+        );
+    }
+
+    commands.insert_resource(sheets);
+}
@@ -0,0 +1,68 @@
+use bevy::{platform::collections::HashMap, prelude::*};
+use serde::Deserialize;
+
+use crate::player::components::PlayableCharacterData;
+
+/// Estadísticas de movimiento y combate de un personaje, del bloque `stats` de
+/// `assets/game_characters.json`.
+///
+/// `health` va en puntos de daño: medios corazones para los players y golpes de bola de
+/// lana para los enemigos. `points` solo cuenta en los enemigos.
+#[derive(Component, Debug, Clone, Copy, Deserialize)]
+pub struct CharacterStats {
+    pub run_speed: f32,
+    pub jump_velocity: f32,
+    pub health: u32,
+    pub mass: f32,
+    #[serde(default)]
+    pub points: u32,
+}
+
+// Del resto de campos de cada personaje (capa, ruta de Python...) solo interesan la hoja
+// completa con su rejilla, las estadísticas, que no llevan los gatos que solo son
+// jugables, y, en los gatos que se pueden elegir, su bloque `playable`
+#[derive(Debug, Deserialize)]
+pub struct GameCharacterData {
+    pub name: String, // Carpeta del personaje dentro de `assets/characters`
+    #[serde(default)]
+    pub sheet: Option<String>,
+    #[serde(default)]
+    pub width: u32,
+    #[serde(default)]
+    pub height: u32,
+    #[serde(default)]
+    pub rows: u32,
+    #[serde(default)]
+    pub stats: Option<CharacterStats>,
+    #[serde(default)]
+    pub playable: Option<PlayableCharacterData>,
+}
+
+/// Personajes de `assets/game_characters.json`, por su clave en el fichero.
+#[derive(Resource)]
+pub struct GameCharacters(pub HashMap<String, GameCharacterData>);
+
+impl GameCharacters {
+    pub fn stats(&self, key: &str) -> Option<CharacterStats> {
+        self.0.get(key).and_then(|character| character.stats)
+    }
+}
+
+/// Hoja completa de un personaje (`*_full.png`) con su layout: una fila de pie, otra
+/// andando a la izquierda y otra andando a la derecha, de `SHEET_COLUMNS` frames cada una.
+#[derive(Clone)]
+pub struct CharacterSheet {
+    pub image: Handle<Image>,
+    pub layout: Handle<TextureAtlasLayout>,
+}
+
+/// Hojas de todos los personajes, por su clave en `game_characters.json`. Cada hoja tiene
+/// un único layout que comparten todos los sprites que la usan.
+#[derive(Resource, Default)]
+pub struct CharacterSheets(pub HashMap<String, CharacterSheet>);
+
+impl CharacterSheets {
+    pub fn get(&self, key: &str) -> Option<&CharacterSheet> {
+        self.0.get(key)
+    }
+}
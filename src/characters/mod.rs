@@ -0,0 +1,20 @@
+pub mod assets;
+pub mod components;
+
+use bevy::prelude::*;
+
+use crate::characters::assets::{load_character_sheets, load_game_characters};
+
+pub struct CharacterStatsPlugin;
+
+impl Plugin for CharacterStatsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Startup,
+            (
+                load_game_characters,
+                load_character_sheets.after(load_game_characters),
+            ),
+        );
+    }
+}
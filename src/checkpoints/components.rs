@@ -0,0 +1,19 @@
+use bevy::prelude::*;
+
+/// Bandera de checkpoint colocada desde los eventos del nivel.
+#[derive(Component)]
+pub struct Checkpoint {
+    pub index: usize, // Orden de izquierda a derecha
+    pub activated: bool,
+    pub respawn_position: Vec3,
+}
+
+/// Se emite al activar un checkpoint.
+#[derive(Event)]
+pub struct CheckpointReached {
+    pub index: usize,
+}
+
+/// Posición donde reaparece el player al morir.
+#[derive(Resource, Debug, Clone, Copy)]
+pub struct RespawnPoint(pub Vec3);
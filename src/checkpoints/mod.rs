@@ -0,0 +1,37 @@
+pub mod components;
+pub mod systems;
+
+use bevy::prelude::*;
+
+use crate::{
+    checkpoints::{
+        components::{Checkpoint, CheckpointReached},
+        systems::*,
+    },
+    game_state::{GameState, LevelState, is_pause_transition},
+};
+
+pub struct CheckpointsPlugin;
+
+impl Plugin for CheckpointsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<CheckpointReached>()
+            .add_systems(OnEnter(LevelState::LevelLoaded), spawn_checkpoints)
+            .add_systems(
+                Update,
+                activate_checkpoints
+                    .run_if(in_state(GameState::Game))
+                    .run_if(in_state(LevelState::LevelLoaded)),
+            )
+            .add_systems(
+                OnExit(GameState::Game),
+                despawn_checkpoints.run_if(not(is_pause_transition)),
+            );
+    }
+}
+
+fn despawn_checkpoints(mut commands: Commands, query: Query<Entity, With<Checkpoint>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn();
+    }
+}
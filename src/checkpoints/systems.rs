@@ -0,0 +1,78 @@
+use bevy::prelude::*;
+
+use crate::{
+    checkpoints::components::{Checkpoint, CheckpointReached, RespawnPoint},
+    map::{assets::GameAssets, components::LevelEvents},
+    player::{assets::HeroData, components::PlayerCharacter},
+};
+
+const CHECKPOINT_SIZE: Vec2 = Vec2::new(8.0, 48.0);
+const CHECKPOINT_RANGE: f32 = 24.0;
+const INACTIVE_COLOR: Color = Color::srgb(0.8, 0.1, 0.1);
+const ACTIVE_COLOR: Color = Color::srgb(0.2, 0.9, 0.2);
+
+// Spawnea las banderas de checkpoint y sitúa el respawn inicial en el punto de salida
+pub fn spawn_checkpoints(
+    mut commands: Commands,
+    level_events: Res<LevelEvents>,
+    game_assets: Res<GameAssets>,
+    hero_data: Res<HeroData>,
+) {
+    let hero_position = game_assets.tile_to_world(hero_data.x, hero_data.y);
+    commands.insert_resource(RespawnPoint(hero_position.extend(0.0)));
+
+    let mut positions: Vec<Vec2> = level_events
+        .events
+        .iter()
+        .filter(|event| event.path == "Checkpoint")
+        .map(|event| game_assets.tile_to_world(event.x as f32, event.y as f32))
+        .collect();
+    positions.sort_by(|a, b| a.x.total_cmp(&b.x));
+
+    for (index, position) in positions.into_iter().enumerate() {
+        // La bandera se apoya sobre el suelo del tile
+        let offset = (CHECKPOINT_SIZE.y - game_assets.tile_size_px) / 2.0;
+
+        commands.spawn((
+            Sprite {
+                color: INACTIVE_COLOR,
+                custom_size: Some(CHECKPOINT_SIZE),
+                ..default()
+            },
+            Transform::from_xyz(position.x, position.y + offset, 4.0),
+            Checkpoint {
+                index,
+                activated: false,
+                respawn_position: position.extend(0.0),
+            },
+        ));
+    }
+}
+
+// Activa los checkpoints que alcanza cualquier player y guarda su posición como respawn
+pub fn activate_checkpoints(
+    mut respawn_point: ResMut<RespawnPoint>,
+    mut checkpoint_events: EventWriter<CheckpointReached>,
+    mut checkpoint_query: Query<(&mut Checkpoint, &Transform, &mut Sprite)>,
+    player_query: Query<&Transform, With<PlayerCharacter>>,
+) {
+    for (mut checkpoint, transform, mut sprite) in checkpoint_query.iter_mut() {
+        let reached = player_query.iter().any(|player_transform| {
+            player_transform
+                .translation
+                .xy()
+                .distance(transform.translation.xy())
+                <= CHECKPOINT_RANGE
+        });
+        if checkpoint.activated || !reached {
+            continue;
+        }
+
+        checkpoint.activated = true;
+        sprite.color = ACTIVE_COLOR;
+        respawn_point.0 = checkpoint.respawn_position;
+        checkpoint_events.write(CheckpointReached {
+            index: checkpoint.index,
+        });
+    }
+}
@@ -4,4 +4,13 @@ use bevy::prelude::*;
 pub struct CollectibleAssets {
     pub treat: Handle<Image>,
     pub heart: Handle<Image>,
+    pub yarn: Handle<Image>,
+}
+
+pub fn load_collectible_assets(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(CollectibleAssets {
+        treat: asset_server.load("treat.png"),
+        heart: asset_server.load("player/Corazon-Sheet.png"),
+        yarn: asset_server.load("projectiles/wool.png"),
+    });
 }
@@ -0,0 +1,35 @@
+pub mod assets;
+pub mod systems;
+
+use bevy::prelude::*;
+
+use crate::{
+    collectibles::{assets::load_collectible_assets, systems::*},
+    game_state::{GameState, LevelState, is_pause_transition},
+    map::components::CollectibleItem,
+};
+
+pub struct CollectiblesPlugin;
+
+impl Plugin for CollectiblesPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, load_collectible_assets)
+            .add_systems(OnEnter(LevelState::LevelLoaded), spawn_collectibles)
+            .add_systems(
+                Update,
+                (bob_collectibles, pickup_collectibles)
+                    .run_if(in_state(GameState::Game))
+                    .run_if(in_state(LevelState::LevelLoaded)),
+            )
+            .add_systems(
+                OnExit(GameState::Game),
+                despawn_collectibles.run_if(not(is_pause_transition)),
+            );
+    }
+}
+
+fn despawn_collectibles(mut commands: Commands, query: Query<Entity, With<CollectibleItem>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn();
+    }
+}
@@ -0,0 +1,182 @@
+use std::f32::consts::TAU;
+
+use bevy::prelude::*;
+
+use crate::{
+    collectibles::assets::CollectibleAssets,
+    map::{
+        assets::GameAssets,
+        components::{
+            CollectibleItem, CollectibleType, LevelEvents, get_collectible_properties_from_path,
+        },
+    },
+    player::components::{Health, Lives, SpeedBoost, WoolAmmo},
+    save::components::RunStats,
+};
+
+const PICKUP_RADIUS: f32 = 24.0;
+const COLLECTIBLE_SIZE: f32 = 24.0;
+const ONE_UP_COLOR: Color = Color::srgb(1.0, 0.85, 0.2);
+const HEART_CONTAINER_COLOR: Color = Color::srgb(0.55, 0.8, 1.0);
+const HEART_CONTAINER_SIZE: f32 = 32.0;
+
+// Spawnea los coleccionables definidos en los eventos del nivel
+pub fn spawn_collectibles(
+    mut commands: Commands,
+    level_events: Res<LevelEvents>,
+    game_assets: Res<GameAssets>,
+    collectible_assets: Res<CollectibleAssets>,
+    mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+) {
+    let heart_layout = texture_atlas_layouts.add(TextureAtlasLayout::from_grid(
+        UVec2::splat(160),
+        21,
+        1,
+        None,
+        None,
+    ));
+
+    for event in &level_events.events {
+        let Some(properties) = get_collectible_properties_from_path(&event.path) else {
+            continue;
+        };
+
+        let position = game_assets
+            .tile_to_world(event.x as f32, event.y as f32)
+            .extend(5.0);
+
+        let sprite = match properties.collectible_type {
+            CollectibleType::Heart => Sprite {
+                image: collectible_assets.heart.clone(),
+                texture_atlas: Some(TextureAtlas {
+                    layout: heart_layout.clone(),
+                    index: 0,
+                }),
+                custom_size: Some(Vec2::splat(COLLECTIBLE_SIZE)),
+                ..default()
+            },
+            CollectibleType::YarnBall => Sprite {
+                image: collectible_assets.yarn.clone(),
+                custom_size: Some(Vec2::splat(COLLECTIBLE_SIZE)),
+                ..default()
+            },
+            CollectibleType::BoneCookie => Sprite {
+                image: collectible_assets.treat.clone(),
+                custom_size: Some(Vec2::splat(COLLECTIBLE_SIZE)),
+                ..default()
+            },
+            // Corazón dorado
+            CollectibleType::OneUp => Sprite {
+                image: collectible_assets.heart.clone(),
+                texture_atlas: Some(TextureAtlas {
+                    layout: heart_layout.clone(),
+                    index: 0,
+                }),
+                color: ONE_UP_COLOR,
+                custom_size: Some(Vec2::splat(COLLECTIBLE_SIZE)),
+                ..default()
+            },
+            // Corazón azul, algo más grande
+            CollectibleType::HeartContainer => Sprite {
+                image: collectible_assets.heart.clone(),
+                texture_atlas: Some(TextureAtlas {
+                    layout: heart_layout.clone(),
+                    index: 0,
+                }),
+                color: HEART_CONTAINER_COLOR,
+                custom_size: Some(Vec2::splat(HEART_CONTAINER_SIZE)),
+                ..default()
+            },
+        };
+
+        commands.spawn((
+            sprite,
+            Transform::from_translation(position),
+            CollectibleItem {
+                properties,
+                original_position: position,
+                ..default()
+            },
+        ));
+    }
+}
+
+// Animación de flotación de los coleccionables
+pub fn bob_collectibles(time: Res<Time>, mut query: Query<(&mut CollectibleItem, &mut Transform)>) {
+    for (mut item, mut transform) in query.iter_mut() {
+        item.bob_timer.tick(time.delta());
+        let phase = item.bob_timer.fraction() * TAU;
+        transform.translation.y = item.original_position.y + phase.sin() * item.bob_amplitude;
+    }
+}
+
+// Recoge los coleccionables que toca el player y aplica su efecto
+pub fn pickup_collectibles(
+    mut commands: Commands,
+    mut collectible_query: Query<(Entity, &mut CollectibleItem, &Transform)>,
+    // Solo los players tienen Health y WoolAmmo
+    mut player_query: Query<(
+        Entity,
+        &Transform,
+        &mut Health,
+        &mut WoolAmmo,
+        Option<&mut SpeedBoost>,
+    )>,
+    mut run_stats: ResMut<RunStats>,
+    mut lives: ResMut<Lives>,
+) {
+    for (entity, mut item, transform) in collectible_query.iter_mut() {
+        if item.collected {
+            continue;
+        }
+        // Lo recoge el primer player en pie que esté lo bastante cerca
+        let Some((player_entity, _, mut health, mut ammo, mut speed_boost)) = player_query
+            .iter_mut()
+            .find(|(_, player_transform, health, _, _)| {
+                health.current > 0
+                    && player_transform
+                        .translation
+                        .xy()
+                        .distance(transform.translation.xy())
+                        <= PICKUP_RADIUS
+            })
+        else {
+            continue;
+        };
+
+        item.collected = true;
+        let properties = &item.properties;
+
+        match properties.collectible_type {
+            CollectibleType::Heart => {
+                health.current =
+                    (health.current + properties.health_restore.max(0) as u32).min(health.max);
+            }
+            CollectibleType::YarnBall => {
+                run_stats.score += properties.points_value.max(0) as u32;
+                ammo.refill(properties.ammo_restore);
+            }
+            CollectibleType::BoneCookie => match speed_boost.as_mut() {
+                Some(speed_boost) => {
+                    speed_boost.refresh(properties.boost_duration, properties.boost_multiplier)
+                }
+                None => {
+                    commands.entity(player_entity).insert(SpeedBoost::new(
+                        properties.boost_duration,
+                        properties.boost_multiplier,
+                    ));
+                }
+            },
+            CollectibleType::OneUp => {
+                lives.current += properties.extra_lives;
+            }
+            // Sube la vida máxima y llena el contenedor nuevo
+            CollectibleType::HeartContainer => {
+                health.max += properties.max_health_increase;
+                health.current += properties.max_health_increase;
+            }
+        }
+
+        commands.entity(entity).despawn();
+    }
+}
@@ -1,20 +1,58 @@
 use bevy::prelude::*;
 
-/// Componente de marcador para la entidad del puntero (crosshair).
+/// Componente de marcador para la entidad del puntero (crosshair). Cada player tiene el
+/// suyo, con su `PlayerId`.
 #[derive(Component)]
 pub struct Crosshair;
 
-/// Componente de marcador para la entidad de la línea de apuntado.
+/// Componente de marcador para los puntos de la línea de apuntado. Cada player tiene los
+/// suyos, con su `PlayerId`.
 #[derive(Component)]
 pub struct AimingLine;
 
 #[derive(Component)]
 pub struct WoolBall;
 
+/// Dispositivo con el que se apunta: el ratón mueve el puntero por la pantalla; el
+/// stick derecho y las teclas de apuntar lo hacen girar alrededor del player. Se empieza
+/// con teclado, que no necesita ratón, y el ratón solo toma el control al moverlo.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum AimDevice {
+    Mouse,
+    Gamepad,
+    #[default]
+    Keyboard,
+}
+
+/// Cómo apunta el player dueño del puntero. En co-op el jugador 1 apunta con ratón o
+/// teclado y el 2 con mando; en solitario decide el último dispositivo usado.
+#[derive(Component, Debug)]
+pub struct AimState {
+    pub device: AimDevice,
+    pub direction: Vec2, // Dirección de apuntado con teclado o mando
+    pub elevation: f32,  // Ángulo sobre la horizontal al apuntar con teclado, en radianes
+    pub lock_on: bool,   // Fijado suave al enemigo más cercano
+}
+
+impl Default for AimState {
+    fn default() -> Self {
+        Self {
+            device: AimDevice::default(),
+            direction: Vec2::X,
+            elevation: 0.0,
+            lock_on: false,
+        }
+    }
+}
+
 /// Componente para gestionar el estado de un proyectil.
+///
+/// Tras el primer choque la bola se va frenando; cuando queda parada se puede recoger
+/// pasando por encima.
 #[derive(Component)]
 pub struct Projectile {
     pub velocity: Vec2,
-    pub despawn_timer: Timer,
     pub has_collided: bool,
+    pub resting: bool,
+    pub damage: u32, // Se gasta al dar a un enemigo: cada bola golpea una sola vez
 }
@@ -1,20 +1,31 @@
 mod assets;
-mod components;
-mod systems;
+pub mod components;
+pub mod systems;
 
 use bevy::{prelude::*, window::PrimaryWindow};
 
 use crate::{
     cursor::{
         assets::{CursorAssets, load_assets},
-        components::{Crosshair, WoolBall},
-        systems::{handle_projectile_despawn, spawn_projectile_on_click, update_aim_assist},
+        components::{AimState, AimingLine, Crosshair},
+        systems::{
+            AIMING_DOTS, CROSSHAIR_FRAME, handle_projectile_despawn, pickup_resting_wool_balls,
+            spawn_projectile_on_click, update_aim_assist, update_aim_direction, update_aiming_line,
+        },
     },
-    game_state::GameState,
+    game_state::{GameState, is_pause_transition},
     map::assets::GameAssets,
-    player::assets::HeroData,
+    player::{
+        assets::HeroData,
+        components::{PlayerCount, PlayerId},
+        systems::PLAYER_SPAWN_SPACING,
+    },
+    pool::components::{EntityPool, PoolKind, SharedAtlasLayouts},
 };
 
+const AIMING_DOT_SIZE: f32 = 4.0;
+const AIMING_DOT_COLOR: Color = Color::srgba(1.0, 1.0, 1.0, 0.7);
+
 pub struct CursorPlugin;
 
 impl Plugin for CursorPlugin {
@@ -22,20 +33,32 @@ impl Plugin for CursorPlugin {
         app.add_systems(Startup, load_assets)
             .add_systems(
                 OnEnter(GameState::Game),
-                (spawn_aim_assist, hide_system_cursor),
+                (
+                    spawn_aim_assist.run_if(not(is_pause_transition)),
+                    hide_system_cursor,
+                ),
             )
+            .add_systems(OnEnter(GameState::PauseMenu), show_system_cursor)
             .add_systems(OnEnter(GameState::MainMenu), show_system_cursor)
             .add_systems(OnEnter(GameState::GameOver), show_system_cursor)
+            .add_systems(OnEnter(GameState::LevelComplete), show_system_cursor)
             .add_systems(
                 Update,
                 (
-                    update_aim_assist,
+                    update_aim_direction,
+                    update_aim_assist.after(update_aim_direction),
                     spawn_projectile_on_click.after(update_aim_assist),
+                    update_aiming_line.after(update_aim_assist),
                     handle_projectile_despawn.after(spawn_projectile_on_click),
+                    pickup_resting_wool_balls.after(handle_projectile_despawn),
                 )
                     .run_if(in_state(GameState::Game)),
             )
-            .add_systems(OnExit(GameState::Game), despawn_cursor);
+            .add_systems(
+                OnExit(GameState::Game),
+                (despawn_cursor, despawn_aiming_line, release_wool_balls)
+                    .run_if(not(is_pause_transition)),
+            );
     }
 }
 
@@ -45,6 +68,18 @@ pub fn despawn_cursor(mut commands: Commands, query: Query<Entity, With<Crosshai
     }
 }
 
+pub fn despawn_aiming_line(mut commands: Commands, query: Query<Entity, With<AimingLine>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
+// Las bolas paradas se quedan en el nivel hasta que alguien las recoge; al salir vuelven
+// todas al pool
+pub fn release_wool_balls(mut commands: Commands, mut pool: ResMut<EntityPool>) {
+    pool.release_all(&mut commands, PoolKind::WoolBall);
+}
+
 pub fn hide_system_cursor(mut window_query: Query<&mut Window, With<PrimaryWindow>>) {
     if let Ok(mut window) = window_query.single_mut() {
         window.cursor_options.visible = false;
@@ -59,10 +94,11 @@ pub fn show_system_cursor(mut window_query: Query<&mut Window, With<PrimaryWindo
 
 pub fn spawn_aim_assist(
     mut commands: Commands,
-    mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+    atlas_layouts: Res<SharedAtlasLayouts>,
     game_assets: Res<GameAssets>,
     hero_data: Res<HeroData>,
     cursor_assets: Res<CursorAssets>,
+    player_count: Res<PlayerCount>,
 ) {
     let tile_size_from_json = game_assets.tile_size_px;
     let map_width_from_json = game_assets.map_width_tiles;
@@ -81,19 +117,38 @@ pub fn spawn_aim_assist(
     transform.translation.y = world_y - tile_size_from_json / 2.0;
     transform.translation.z = 100.0;
 
-    let layout = TextureAtlasLayout::from_grid(UVec2::splat(16), 5, 1, None, None);
-    let texture_atlas_layout = texture_atlas_layouts.add(layout);
-
-    commands.spawn((
-        Sprite {
-            image: cursor_assets.cursor_image.clone(), // Reemplaza con tu imagen
-            texture_atlas: Some(TextureAtlas {
-                layout: texture_atlas_layout.clone(),
-                index: 4,
-            }),
-            ..default()
-        },
-        transform,
-        Crosshair,
-    ));
+    for player in (0..player_count.0).map(PlayerId) {
+        let mut transform = transform;
+        transform.translation.x += player.0 as f32 * PLAYER_SPAWN_SPACING;
+
+        commands.spawn((
+            Sprite {
+                image: cursor_assets.cursor_image.clone(), // Reemplaza con tu imagen
+                texture_atlas: Some(TextureAtlas {
+                    layout: atlas_layouts.crosshair.clone(),
+                    index: CROSSHAIR_FRAME,
+                }),
+                ..default()
+            },
+            transform,
+            Crosshair,
+            player,
+            AimState::default(),
+        ));
+
+        // Línea de apuntado: puntos sueltos que se colocan cada frame sobre la parábola
+        for _ in 0..AIMING_DOTS {
+            commands.spawn((
+                Sprite {
+                    color: AIMING_DOT_COLOR,
+                    custom_size: Some(Vec2::splat(AIMING_DOT_SIZE)),
+                    ..default()
+                },
+                Transform::from_xyz(0.0, 0.0, 97.0),
+                Visibility::Hidden,
+                AimingLine,
+                player,
+            ));
+        }
+    }
 }
@@ -1,33 +1,184 @@
-use bevy::{prelude::*, window::PrimaryWindow};
+use std::f32::consts::FRAC_PI_2;
+
+use bevy::{ecs::system::SystemParam, prelude::*, window::PrimaryWindow};
 use bevy_rapier2d::prelude::{
-    ActiveEvents, Collider, CollisionEvent, RigidBody, Sleeping, Velocity,
+    ActiveEvents, CoefficientCombineRule, Collider, CollisionEvent, Damping, QueryFilter,
+    ReadRapierContext, Restitution, RigidBody, Sensor, Sleeping, Velocity,
 };
 
 use crate::{
-    cursor::{
-        assets::CursorAssets,
-        components::{Crosshair, Projectile, WoolBall},
-    },
+    animation::components::{AnimationState, CharacterAnimator},
+    cursor::components::{AimDevice, AimState, AimingLine, Crosshair, Projectile, WoolBall},
+    enemies::components::{EnemyCharacter, EnemyDefeated},
+    input::components::{Action, PlayerActions},
     physics::{AffectedByGravity, Mass},
-    player::components::PlayerCharacter,
+    player::components::{
+        Downed, PlayerCharacter, PlayerCount, PlayerId, PlayerProjectile, ThrowMode, WoolAmmo,
+    },
+    pool::components::{EntityPool, Inactive, PoolKind},
 };
 
+const GAMEPAD_AIM_DEADZONE: f32 = 0.3;
+// Distancia del puntero al player cuando se apunta con teclado o mando
+const AIM_RADIUS: f32 = 150.0;
+const KEYBOARD_AIM_SPEED: f32 = 2.0; // rad/s
+// Fijado suave: alcance y ángulo máximo entre la dirección de apuntado y el enemigo
+const LOCK_ON_RANGE: f32 = 450.0;
+const LOCK_ON_CONE: f32 = 0.5; // rad
+const PROJECTILE_SPRITE_SIZE: f32 = 64.0;
+const WOOL_BALL_DAMAGE: u32 = 1;
+// Segundos que hay que mantener el botón para cargar el lanzamiento del todo
+const THROW_CHARGE_TIME: f32 = 0.8;
+const THROW_GRAVITY: f32 = 9.81 * 32.0; // px/s², la misma gravedad que aplica Rapier
+const THROW_OFFSET: f32 = 32.0; // La bola sale por delante del player
+// Frames del atlas del puntero: diana normal y tachada cuando el lanzamiento no llega
+pub const CROSSHAIR_FRAME: usize = 4;
+const CROSSHAIR_UNREACHABLE_FRAME: usize = 2;
+// Puntos de la línea de apuntado y tiempo de vuelo simulado entre dos puntos seguidos
+pub const AIMING_DOTS: usize = 24;
+const AIMING_DOT_STEP: f32 = 0.04;
+// Frenado de la bola tras el primer choque, y velocidad por debajo de la cual se queda quieta
+const WOOL_BALL_DAMPING: f32 = 3.0;
+const WOOL_BALL_REST_SPEED: f32 = 20.0;
+const WOOL_BALL_PICKUP_RADIUS: f32 = 32.0;
+
+// Estado del ratón para elegir el dispositivo de apuntado
+#[derive(SystemParam)]
+pub struct MouseActivity<'w, 's> {
+    cursor_moved: EventReader<'w, 's, CursorMoved>,
+    window_query: Query<'w, 's, &'static Window, With<PrimaryWindow>>,
+}
+
+impl MouseActivity<'_, '_> {
+    fn moved(&mut self) -> bool {
+        self.cursor_moved.read().count() > 0
+    }
+
+    // Sin el cursor en la ventana el puntero se quedaría clavado, así que se vuelve al teclado
+    fn in_window(&self) -> bool {
+        self.window_query
+            .single()
+            .is_ok_and(|window| window.cursor_position().is_some())
+    }
+}
+
+// Elige con qué dispositivo apunta cada player y actualiza su dirección de apuntado.
+// El teclado sube o baja el ángulo hacia el lado al que mira el player; el stick derecho
+// da la dirección directamente
+pub fn update_aim_direction(
+    mut mouse: MouseActivity,
+    mut crosshair_query: Query<(&PlayerId, &mut AimState), With<Crosshair>>,
+    player_query: Query<(&PlayerId, &CharacterAnimator), With<PlayerCharacter>>,
+    gamepads: Query<&Gamepad>,
+    player_actions: Res<PlayerActions>,
+    player_count: Res<PlayerCount>,
+    time: Res<Time>,
+) {
+    let mouse_moved = mouse.moved();
+    let cursor_in_window = mouse.in_window();
+    let stick = gamepads
+        .iter()
+        .map(|gamepad| gamepad.right_stick())
+        .find(|stick| stick.length() > GAMEPAD_AIM_DEADZONE);
+
+    for (player, mut aim_state) in crosshair_query.iter_mut() {
+        let actions = player_actions.get(player);
+        if actions.just_pressed(Action::ToggleLockOn) {
+            aim_state.lock_on = !aim_state.lock_on;
+        }
+
+        // En co-op el jugador 2 apunta con mando; el 1 y el de solitario, con el último
+        // dispositivo que hayan usado
+        let aim_keys =
+            actions.pressed(Action::AimUp) as i32 - actions.pressed(Action::AimDown) as i32;
+        if player_count.is_coop() && player.0 != 0 {
+            aim_state.device = AimDevice::Gamepad;
+        } else if mouse_moved {
+            aim_state.device = AimDevice::Mouse;
+        } else if aim_keys != 0 || (aim_state.device == AimDevice::Mouse && !cursor_in_window) {
+            aim_state.device = AimDevice::Keyboard;
+        }
+
+        match aim_state.device {
+            AimDevice::Gamepad => {
+                if let Some(stick) = stick {
+                    aim_state.direction = stick.normalize();
+                }
+            }
+            AimDevice::Keyboard => {
+                let Some((_, animator)) = player_query.iter().find(|(owner, _)| *owner == player)
+                else {
+                    continue;
+                };
+                aim_state.elevation = (aim_state.elevation
+                    + aim_keys as f32 * KEYBOARD_AIM_SPEED * time.delta_secs())
+                .clamp(-FRAC_PI_2, FRAC_PI_2);
+                aim_state.direction = Vec2::new(
+                    aim_state.elevation.cos() * animator.facing,
+                    aim_state.elevation.sin(),
+                );
+            }
+            AimDevice::Mouse => {}
+        }
+
+        // En solitario el stick toma el control en cuanto se mueve
+        if !player_count.is_coop()
+            && let Some(stick) = stick
+        {
+            aim_state.device = AimDevice::Gamepad;
+            aim_state.direction = stick.normalize();
+        }
+    }
+}
+
+// Coloca el puntero de cada player: bajo el ratón, o a distancia fija del player en la
+// dirección de apuntado. Con el fijado suave, el puntero salta al enemigo más cercano que
+// quede a tiro y cerca de esa dirección
 pub fn update_aim_assist(
-    // Recursos para obtener la posición del ratón
-    // mut evr_cursor: EventReader<CursorMoved>,
     window: Single<&Window, With<PrimaryWindow>>,
     camera_query: Query<(&Camera, &GlobalTransform), With<Camera2d>>,
-    mut crosshair_query: Query<&mut Transform, With<Crosshair>>,
+    mut crosshair_query: Query<(&PlayerId, &mut Transform, &AimState), With<Crosshair>>,
+    player_query: Query<(&PlayerId, &GlobalTransform), With<PlayerCharacter>>,
+    enemy_query: Query<&GlobalTransform, (With<EnemyCharacter>, Without<EnemyDefeated>)>,
 ) {
-    if let Some(position) = window.cursor_position() {
-        if let Ok((camera, camera_transform)) = camera_query.single() {
-            if let Ok(world_position) = camera.viewport_to_world_2d(camera_transform, position) {
-                for mut crosshair_transform in crosshair_query.iter_mut() {
-                    crosshair_transform.translation.x = world_position.x;
-                    crosshair_transform.translation.y = world_position.y;
-                }
+    let mouse_world_position = window.cursor_position().and_then(|position| {
+        let (camera, camera_transform) = camera_query.single().ok()?;
+        camera.viewport_to_world_2d(camera_transform, position).ok()
+    });
+
+    for (player, mut crosshair_transform, aim_state) in crosshair_query.iter_mut() {
+        let target = match aim_state.device {
+            AimDevice::Gamepad | AimDevice::Keyboard => {
+                let Some((_, player_transform)) =
+                    player_query.iter().find(|(owner, _)| *owner == player)
+                else {
+                    continue;
+                };
+                let player_position = player_transform.translation().xy();
+                let locked_enemy = enemy_query
+                    .iter()
+                    .map(|enemy| enemy.translation().xy())
+                    .filter(|enemy| {
+                        let offset = *enemy - player_position;
+                        aim_state.lock_on
+                            && offset.length() <= LOCK_ON_RANGE
+                            && aim_state.direction.angle_to(offset).abs() <= LOCK_ON_CONE
+                    })
+                    .min_by(|a, b| {
+                        a.distance_squared(player_position)
+                            .total_cmp(&b.distance_squared(player_position))
+                    });
+                locked_enemy.unwrap_or(player_position + aim_state.direction * AIM_RADIUS)
             }
-        }
+            AimDevice::Mouse => {
+                let Some(world_position) = mouse_world_position else {
+                    continue;
+                };
+                world_position
+            }
+        };
+        crosshair_transform.translation.x = target.x;
+        crosshair_transform.translation.y = target.y;
     }
 }
 
@@ -72,23 +223,157 @@ fn solve_ballistic_velocity(
     Some(Vec2::new(vx, vy))
 }
 
+// Velocidad con la que sale la bola para caer en el puntero, si llega con esa rapidez
+fn throw_velocity(start: Vec2, target: Vec2, speed: f32, high_arc: bool) -> Option<Vec2> {
+    solve_ballistic_velocity(start, target, speed, THROW_GRAVITY, high_arc)
+}
+
+// Dibuja la parábola que seguirá la bola, desde el player hasta el puntero o hasta el
+// primer tile con el que choque. Si el lanzamiento no llega, tacha el puntero
+pub fn update_aiming_line(
+    rapier_context: ReadRapierContext,
+    // Solo los players tienen PlayerProjectile y ThrowMode
+    player_query: Query<(
+        &PlayerId,
+        &GlobalTransform,
+        &PlayerProjectile,
+        &ThrowMode,
+        Has<Downed>,
+    )>,
+    mut crosshair_query: Query<(&PlayerId, &GlobalTransform, &mut Sprite), With<Crosshair>>,
+    mut dot_query: Query<(&PlayerId, &mut Transform, &mut Visibility), With<AimingLine>>,
+) {
+    let Ok(context) = rapier_context.single() else {
+        return;
+    };
+    // Solo cuentan los tiles: las bolas paradas son sensores y los personajes no son fijos
+    let filter = QueryFilter::only_fixed().exclude_sensors();
+
+    for (player, player_transform, projectile, throw_mode, downed) in player_query.iter() {
+        let Some((_, crosshair_transform, mut crosshair_sprite)) = crosshair_query
+            .iter_mut()
+            .find(|(owner, _, _)| *owner == player)
+        else {
+            continue;
+        };
+
+        let start_pos = player_transform.translation().xy();
+        let target_pos = crosshair_transform.translation().xy();
+        // Sin cargar se previsualiza el lanzamiento de un toque, a la velocidad mínima
+        let speed = projectile.projectile_type.speed(throw_mode.charge);
+        let velocity = throw_velocity(start_pos, target_pos, speed, throw_mode.high_arc);
+
+        if let Some(atlas) = &mut crosshair_sprite.texture_atlas {
+            atlas.index = if velocity.is_some() {
+                CROSSHAIR_FRAME
+            } else {
+                CROSSHAIR_UNREACHABLE_FRAME
+            };
+        }
+
+        let mut points = Vec::with_capacity(AIMING_DOTS);
+        if let Some(velocity) = velocity.filter(|_| !downed) {
+            let start = start_pos + velocity.normalize() * THROW_OFFSET;
+            let flight_time = if velocity.x.abs() > 1.0 {
+                (target_pos.x - start.x) / velocity.x
+            } else {
+                (target_pos.y - start.y).abs() / speed
+            };
+            // Los arcos altos tardan más: los puntos se separan para cubrir todo el vuelo
+            let step_time = (flight_time / AIMING_DOTS as f32).max(AIMING_DOT_STEP);
+            let gravity = Vec2::new(0.0, -THROW_GRAVITY);
+
+            let mut previous = start;
+            for step in 1..=AIMING_DOTS {
+                let t = step as f32 * step_time;
+                if t > flight_time {
+                    break;
+                }
+                let point = start + velocity * t + 0.5 * gravity * t * t;
+                let segment = point - previous;
+                if let Some((_, toi)) = context.cast_ray(previous, segment, 1.0, true, filter) {
+                    points.push(previous + segment * toi);
+                    break;
+                }
+                points.push(point);
+                previous = point;
+            }
+        }
+
+        // Todos los puntos son iguales: se colocan en orden y sobran los demás
+        let mut points = points.into_iter();
+        for (owner, mut transform, mut visibility) in dot_query.iter_mut() {
+            if owner != player {
+                continue;
+            }
+            match points.next() {
+                Some(point) => {
+                    transform.translation.x = point.x;
+                    transform.translation.y = point.y;
+                    *visibility = Visibility::Visible;
+                }
+                None => *visibility = Visibility::Hidden,
+            }
+        }
+    }
+}
+
 pub fn spawn_projectile_on_click(
     mut commands: Commands,
-    mouse_button_input: Res<ButtonInput<MouseButton>>,
-    cursor_assets: Res<CursorAssets>,
-    player_query: Query<&Transform, With<PlayerCharacter>>,
-    crosshair_query: Query<&Transform, With<Crosshair>>,
-    mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+    mut pool: ResMut<EntityPool>,
+    time: Res<Time>,
+    player_actions: Res<PlayerActions>,
+    mut player_query: Query<
+        (
+            Entity,
+            &PlayerId,
+            &PlayerProjectile,
+            &mut WoolAmmo,
+            &mut ThrowMode,
+        ),
+        // Solo los players tienen PlayerProjectile; los caídos no lanzan
+        Without<Downed>,
+    >,
+    mut animator_query: Query<(&Transform, &mut CharacterAnimator)>,
+    crosshair_query: Query<(&PlayerId, &Transform), With<Crosshair>>,
 ) {
-    if mouse_button_input.just_pressed(MouseButton::Left) {
-        let layout = TextureAtlasLayout::from_grid(UVec2::splat(64), 1, 1, None, None);
-        let texture_atlas_layout = texture_atlas_layouts.add(layout);
+    for (entity, player, projectile, mut ammo, mut throw_mode) in player_query.iter_mut() {
+        ammo.cooldown.tick(time.delta());
+        let actions = player_actions.get(player);
+
+        if actions.just_pressed(Action::ToggleArc) {
+            throw_mode.high_arc = !throw_mode.high_arc;
+        }
+
+        // Mantener el botón carga el lanzamiento; la bola sale al soltarlo
+        if actions.just_pressed(Action::Throw) && ammo.can_throw() {
+            throw_mode.charging = true;
+            throw_mode.charge = 0.0;
+        }
+        if !throw_mode.charging {
+            continue;
+        }
+        if actions.pressed(Action::Throw) {
+            throw_mode.charge =
+                (throw_mode.charge + time.delta_secs() / THROW_CHARGE_TIME).min(1.0);
+            continue;
+        }
+        let speed = projectile.projectile_type.speed(throw_mode.charge);
+        throw_mode.charging = false;
+        throw_mode.charge = 0.0;
+        // Si se soltó mientras estaba caído, la carga se pierde sin lanzar
+        if !actions.just_released(Action::Throw) {
+            continue;
+        }
 
-        let Ok(player_transform) = player_query.single() else {
-            return;
+        // Cada player lanza hacia su propio puntero
+        let Some((_, crosshair_transform)) =
+            crosshair_query.iter().find(|(owner, _)| *owner == player)
+        else {
+            continue;
         };
-        let Ok(crosshair_transform) = crosshair_query.single() else {
-            return;
+        let Ok((player_transform, mut animator)) = animator_query.get_mut(entity) else {
+            continue;
         };
 
         // let player_position = player_transform.translation.xy();
@@ -105,82 +390,183 @@ pub fn spawn_projectile_on_click(
         let start_pos = player_transform.translation.xy();
         let target_pos = crosshair_transform.translation.xy();
 
-        let speed = 1000.0;
-        let gravity = 9.81 * 32.0; // px/s² (ajusta según tus unidades)
-        let prefer_high_arc = false;
+        if let Some(velocity) = throw_velocity(start_pos, target_pos, speed, throw_mode.high_arc) {
+            let offset_pos = start_pos + velocity.normalize() * THROW_OFFSET;
 
-        if let Some(velocity) =
-            solve_ballistic_velocity(start_pos, target_pos, speed, gravity, prefer_high_arc)
-        {
-            // desplazamiento hacia adelante (por ejemplo, 32 px)
-            let offset_dist = 32.0;
-            let offset_pos = start_pos + velocity.normalize() * offset_dist;
-
-            commands.spawn((
-                Sprite {
-                    image: cursor_assets.wool_image.clone(),
-                    texture_atlas: Some(TextureAtlas {
-                        layout: texture_atlas_layout.clone(),
-                        index: 0,
-                    }),
-                    ..default()
-                },
-                Transform::from_translation(offset_pos.extend(98.0)).with_scale(Vec3::splat(0.5)),
-                WoolBall,
-                Projectile {
-                    velocity,
-                    despawn_timer: Timer::from_seconds(2.0, TimerMode::Once),
-                    has_collided: false,
-                },
-                Collider::ball(8.0), // Tamaño del colisionador de la bola
-                RigidBody::Dynamic,
-                Velocity {
-                    linvel: velocity,
-                    angvel: 0.0,
-                },
-                ActiveEvents::COLLISION_EVENTS, // Habilita la detección de colisiones
-                AffectedByGravity,
-                Mass { kilograms: 10.0 },
-                Sleeping::default(),
-            ));
+            ammo.current -= 1;
+            ammo.cooldown.reset();
+
+            // El player se gira hacia donde lanza
+            animator.facing = if velocity.x < 0.0 { -1.0 } else { 1.0 };
+            animator.play_once(AnimationState::Throw);
+
+            // Las bolas se reciclan: el collider y los componentes fijos se quedan, y solo
+            // se reinicia el estado del lanzamiento
+            let entity = pool
+                .reuse(&mut commands, PoolKind::WoolBall)
+                .unwrap_or_else(|| {
+                    let entity = commands
+                        .spawn((
+                            WoolBall,
+                            Collider::ball(8.0), // Tamaño del colisionador de la bola
+                            ActiveEvents::COLLISION_EVENTS, // Habilita la detección de colisiones
+                            AffectedByGravity,
+                            Mass { kilograms: 10.0 },
+                        ))
+                        .id();
+                    pool.track(PoolKind::WoolBall, entity);
+                    entity
+                });
+            commands
+                .entity(entity)
+                .remove::<(Damping, Sensor)>()
+                .insert((
+                    Sprite {
+                        image: projectile.image.clone(),
+                        // Las hojas de proyectiles no miden lo mismo; todas se dibujan a 64 px
+                        custom_size: Some(Vec2::splat(PROJECTILE_SPRITE_SIZE)),
+                        ..default()
+                    },
+                    Transform::from_translation(offset_pos.extend(98.0))
+                        .with_scale(Vec3::splat(0.5)),
+                    Projectile {
+                        velocity,
+                        has_collided: false,
+                        resting: false,
+                        damage: WOOL_BALL_DAMAGE,
+                    },
+                    RigidBody::Dynamic,
+                    Velocity {
+                        linvel: velocity,
+                        angvel: 0.0,
+                    },
+                    Restitution {
+                        coefficient: projectile.projectile_type.restitution(),
+                        combine_rule: CoefficientCombineRule::Max, // Los tiles no rebotan
+                    },
+                    Sleeping::default(),
+                ));
         }
     }
 }
 
-/// Sistema para gestionar las colisiones de los proyectiles y el temporizador de desaparición.
+/// Sistema para gestionar las colisiones de los proyectiles: tras el primer choque la bola
+/// se frena y, cuando casi no se mueve, se queda quieta esperando a que la recojan.
 pub fn handle_projectile_despawn(
     mut commands: Commands,
-    mut projectile_query: Query<(Entity, &mut Projectile)>,
+    mut projectile_query: Query<(Entity, &mut Projectile, &mut Velocity), Without<Inactive>>,
     mut collision_events: EventReader<CollisionEvent>,
-    time: Res<Time>,
 ) {
     for event in collision_events.read() {
         if let CollisionEvent::Started(entity1, entity2, _) = event {
             // Revisa si alguna de las entidades es un proyectil.
-            if let Ok((_, mut projectile)) = projectile_query.get_mut(*entity1) {
-                if !projectile.has_collided {
-                    projectile.has_collided = true;
-                    println!("Projectile collided");
-                    // Aquí puedes añadir efectos o sonido
-                }
-            }
-            if let Ok((_, mut projectile)) = projectile_query.get_mut(*entity2) {
-                if !projectile.has_collided {
+            for entity in [*entity1, *entity2] {
+                if let Ok((_, mut projectile, _)) = projectile_query.get_mut(entity)
+                    && !projectile.has_collided
+                {
                     projectile.has_collided = true;
-                    println!("Projectile collided");
-                    // Aquí puedes añadir efectos o sonido
+                    commands.entity(entity).insert(Damping {
+                        linear_damping: WOOL_BALL_DAMPING,
+                        angular_damping: WOOL_BALL_DAMPING,
+                    });
                 }
             }
         }
     }
 
-    // Si un proyectil ha colisionado, su temporizador se inicia.
-    for (entity, mut projectile) in projectile_query.iter_mut() {
-        if projectile.has_collided {
-            projectile.despawn_timer.tick(time.delta());
-            if projectile.despawn_timer.finished() {
-                commands.entity(entity).despawn();
-            }
+    for (entity, mut projectile, mut velocity) in projectile_query.iter_mut() {
+        if !projectile.has_collided
+            || projectile.resting
+            || velocity.linvel.length() > WOOL_BALL_REST_SPEED
+        {
+            continue;
+        }
+        // Parada del todo y sin bloquear al player, que pasa por encima para recogerla
+        projectile.resting = true;
+        projectile.damage = 0; // Una bola parada ya no hace daño
+        *velocity = Velocity::zero();
+        commands.entity(entity).insert((RigidBody::Fixed, Sensor));
+    }
+}
+
+// Un player que pasa junto a una bola parada la recupera si no lleva la lana al máximo
+pub fn pickup_resting_wool_balls(
+    mut commands: Commands,
+    mut pool: ResMut<EntityPool>,
+    ball_query: Query<(Entity, &Projectile, &Transform), Without<Inactive>>,
+    mut player_query: Query<(&Transform, &mut WoolAmmo), Without<Downed>>,
+) {
+    for (entity, projectile, ball_transform) in ball_query.iter() {
+        if !projectile.resting {
+            continue;
         }
+        let ball_position = ball_transform.translation.xy();
+        let Some((_, mut ammo)) = player_query.iter_mut().find(|(player_transform, ammo)| {
+            ammo.current < ammo.max
+                && player_transform.translation.xy().distance(ball_position)
+                    <= WOOL_BALL_PICKUP_RADIUS
+        }) else {
+            continue;
+        };
+        ammo.refill(1);
+        pool.release(&mut commands, PoolKind::WoolBall, entity);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Altura de la bola al llegar a la x del objetivo
+    fn height_at_target(start: Vec2, target: Vec2, velocity: Vec2) -> f32 {
+        let t = (target.x - start.x) / velocity.x;
+        start.y + velocity.y * t - 0.5 * THROW_GRAVITY * t * t
+    }
+
+    #[test]
+    fn both_arcs_reach_the_target() {
+        let start = Vec2::new(10.0, 20.0);
+        let target = Vec2::new(210.0, 60.0);
+        let speed = 400.0;
+
+        let low = throw_velocity(start, target, speed, false).unwrap();
+        let high = throw_velocity(start, target, speed, true).unwrap();
+
+        assert!((low.length() - speed).abs() < 1e-2);
+        assert!((high.length() - speed).abs() < 1e-2);
+        assert!(high.y > low.y);
+        assert!((height_at_target(start, target, low) - target.y).abs() < 0.5);
+        assert!((height_at_target(start, target, high) - target.y).abs() < 0.5);
+    }
+
+    #[test]
+    fn throws_towards_the_target_side() {
+        let start = Vec2::ZERO;
+        let target = Vec2::new(-150.0, 0.0);
+
+        let velocity = throw_velocity(start, target, 300.0, false).unwrap();
+
+        assert!(velocity.x < 0.0);
+        assert!((height_at_target(start, target, velocity) - target.y).abs() < 0.5);
+    }
+
+    #[test]
+    fn out_of_range_target_is_unreachable() {
+        assert_eq!(
+            throw_velocity(Vec2::ZERO, Vec2::new(5000.0, 0.0), 100.0, false),
+            None
+        );
+    }
+
+    #[test]
+    fn vertical_throw_goes_straight_up_or_down() {
+        assert_eq!(
+            throw_velocity(Vec2::ZERO, Vec2::new(0.0, 50.0), 200.0, false),
+            Some(Vec2::new(0.0, 200.0))
+        );
+        assert_eq!(
+            throw_velocity(Vec2::ZERO, Vec2::new(0.0, -50.0), 200.0, false),
+            Some(Vec2::new(0.0, -200.0))
+        );
     }
 }
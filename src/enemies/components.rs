@@ -1,9 +1,8 @@
 use crate::map::components::TilePosition;
 use bevy::{
     asset::Handle,
+    audio::AudioSource,
     ecs::{component::Component, resource::Resource},
-    image::Image,
-    platform::collections::HashMap,
     time::Timer,
 };
 use serde::Deserialize;
@@ -19,15 +18,24 @@ pub enum EnemyType {
     Willie,
 }
 
-pub struct EnemyAssetSet {
-    pub texture_standing: Handle<Image>,
-    pub texture_left: Handle<Image>,
-    pub texture_right: Handle<Image>,
+impl EnemyType {
+    // Clave del enemigo en `assets/game_characters.json`
+    pub fn character_key(&self) -> &'static str {
+        match self {
+            EnemyType::Catcifer => "maniac",
+            EnemyType::Dummy => "dummy",
+            EnemyType::Fufi => "turret",
+            EnemyType::KiddCat => "boss",
+            EnemyType::Maximiliano => "maximiliano",
+            EnemyType::Willie => "willie",
+        }
+    }
 }
 
 #[derive(Resource)]
 pub struct EnemyAssets {
-    pub map: HashMap<EnemyType, EnemyAssetSet>,
+    pub hurt_sound: Handle<AudioSource>,
+    pub defeat_sound: Handle<AudioSource>,
 }
 
 #[derive(Debug, Deserialize, Resource)]
@@ -45,6 +53,28 @@ pub struct ActiveLevenData {
 #[derive(Component)]
 pub struct EnemyCharacter;
 
+/// Vida de un enemigo, en golpes de bola de lana.
+#[derive(Component, Debug)]
+pub struct EnemyHealth {
+    pub current: u32,
+}
+
+/// Puntos que da un enemigo al ser derrotado.
+#[derive(Component)]
+pub struct EnemyPoints(pub u32);
+
+/// Destello de daño de un enemigo tras recibir un golpe.
+#[derive(Component)]
+pub struct EnemyHurt {
+    pub timer: Timer,
+}
+
+/// Enemigo derrotado: ya no hace daño y se desvanece hasta desaparecer.
+#[derive(Component)]
+pub struct EnemyDefeated {
+    pub timer: Timer,
+}
+
 // --- Componentes de IA ---
 
 /// Define el estado actual de la IA de un enemigo.
@@ -55,6 +85,7 @@ pub enum EnemyState {
     Chasing,
     Attacking,
     Fleeing, // Para teletransportarse
+    Dead,
 }
 
 /// Componente para enemigos que patrullan.
@@ -1,24 +1,35 @@
 use std::time::Duration;
 
 use crate::{
+    animation::{
+        components::{CharacterAnimator, CharacterSprite},
+        systems::animate_characters,
+    },
+    characters::{
+        assets::CHARACTER_SPRITE_SIZE,
+        components::{CharacterSheets, GameCharacters},
+    },
     enemies::{
         assets::load_enemy_assets,
         bundle::EnemyBundle,
         components::{
-            ActiveLevenData, Chase, ContactDamage, EnemyAssets, EnemyCharacter, EnemyState,
-            EnemyType, Patrol, RangedAttack, RangedAttackType, Teleport,
+            ActiveLevenData, Chase, ContactDamage, EnemyCharacter, EnemyHealth, EnemyPoints,
+            EnemyState, EnemyType, Patrol, RangedAttack, RangedAttackType, Teleport,
+        },
+        systems::{
+            enemy_damage_system, enemy_defeat_system, enemy_hurt_flash_system,
+            release_enemy_projectiles, update_enemy_animation, wool_ball_hit_enemy_system,
         },
     },
-    game_state::GameState,
+    game_state::{GameState, is_pause_transition},
     map::assets::GameAssets,
     physics::{AffectedByGravity, Mass, Velocity},
-    player::components::{
-        AnimationIndices, CharacterIdleSprite, CharacterLeftSprite, CharacterRightSprite,
-    },
+    player::systems::apply_player_hits,
 };
 use bevy::prelude::*;
 use bevy_rapier2d::prelude::{
-    ActiveEvents, Collider, KinematicCharacterController, RigidBody, Velocity as RapierVelocity,
+    ActiveCollisionTypes, ActiveEvents, Collider, KinematicCharacterController, RigidBody,
+    Velocity as RapierVelocity,
 };
 
 pub mod assets;
@@ -32,9 +43,26 @@ impl Plugin for EnemiesPlugin {
         app.add_systems(Startup, load_enemy_assets)
             .add_systems(
                 OnEnter(GameState::Game),
-                spawn_enemies_characters.after(load_enemy_assets),
+                spawn_enemies_characters
+                    .after(load_enemy_assets)
+                    .run_if(not(is_pause_transition)),
             )
-            .add_systems(OnExit(GameState::Game), despawn_enemies);
+            .add_systems(
+                Update,
+                (
+                    wool_ball_hit_enemy_system,
+                    update_enemy_animation.after(wool_ball_hit_enemy_system),
+                    enemy_hurt_flash_system.after(wool_ball_hit_enemy_system),
+                    enemy_defeat_system,
+                    enemy_damage_system.before(apply_player_hits),
+                )
+                    .before(animate_characters)
+                    .run_if(in_state(GameState::Game)),
+            )
+            .add_systems(
+                OnExit(GameState::Game),
+                (despawn_enemies, release_enemy_projectiles).run_if(not(is_pause_transition)),
+            );
     }
 }
 pub fn despawn_enemies(mut commands: Commands, query: Query<Entity, With<EnemyCharacter>>) {
@@ -45,22 +73,26 @@ pub fn despawn_enemies(mut commands: Commands, query: Query<Entity, With<EnemyCh
 
 pub fn spawn_enemies_characters(
     mut commands: Commands,
-    enemies_assets: Res<EnemyAssets>,
     enemies_level_data: Res<ActiveLevenData>,
     game_assets: Res<GameAssets>,
-    mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+    game_characters: Res<GameCharacters>,
+    sheets: Res<CharacterSheets>,
 ) {
     let tile_size_from_json = game_assets.tile_size_px;
     let map_width_from_json = game_assets.map_width_tiles;
     let map_height_from_json = game_assets.map_height_tiles;
 
-    let layout = TextureAtlasLayout::from_grid(UVec2::splat(64), 8, 1, None, None);
-    let texture_atlas_layout = texture_atlas_layouts.add(layout);
-
     for enemy in &enemies_level_data.enemies {
         for obj in &enemy.positions {
             let enemy_type: EnemyType = enemy.name.parse().unwrap();
-            let enemy_asset = &enemies_assets.map[&enemy_type];
+            let key = enemy_type.character_key();
+            let (Some(stats), Some(sheet)) = (game_characters.stats(key), sheets.get(key)) else {
+                warn!(
+                    "Character '{key}' needs a sheet and stats in game characters JSON, skipping {}",
+                    enemy.name
+                );
+                continue;
+            };
 
             let x = obj.x as f32;
             let y = obj.y as f32;
@@ -84,56 +116,38 @@ pub fn spawn_enemies_characters(
                 .with_children(|parent| {
                     parent.spawn((
                         Sprite {
-                            image: enemy_asset.texture_left.clone(),
-                            texture_atlas: Some(TextureAtlas {
-                                layout: texture_atlas_layout.clone(),
-                                index: 0,
-                            }),
-                            ..default()
-                        },
-                        sprite_transform,
-                        CharacterLeftSprite,
-                        Visibility::Hidden,
-                        AnimationIndices::new(0, 7, ANIMATION_FPS),
-                    ));
-                    parent.spawn((
-                        Sprite {
-                            image: enemy_asset.texture_right.clone(),
-                            texture_atlas: Some(TextureAtlas {
-                                layout: texture_atlas_layout.clone(),
-                                index: 0,
-                            }),
-                            ..default()
-                        },
-                        sprite_transform,
-                        CharacterRightSprite,
-                        Visibility::Hidden,
-                        AnimationIndices::new(0, 7, ANIMATION_FPS),
-                    ));
-                    parent.spawn((
-                        Sprite {
-                            image: enemy_asset.texture_standing.clone(),
+                            image: sheet.image.clone(),
                             texture_atlas: Some(TextureAtlas {
-                                layout: texture_atlas_layout.clone(),
+                                layout: sheet.layout.clone(),
                                 index: 0,
                             }),
+                            custom_size: Some(Vec2::splat(CHARACTER_SPRITE_SIZE)),
                             ..default()
                         },
                         sprite_transform,
-                        CharacterIdleSprite,
-                        Visibility::Visible,
-                        AnimationIndices::new(0, 7, ANIMATION_FPS),
+                        CharacterSprite,
                     ));
                 })
+                .insert(CharacterAnimator::for_cat())
                 .insert(RigidBody::KinematicPositionBased)
                 .insert(KinematicCharacterController::default())
                 .insert(Collider::ball(32.0 / 2.0))
                 .insert(EnemyCharacter)
+                .insert(EnemyHealth {
+                    current: stats.health,
+                })
+                .insert(EnemyPoints(stats.points))
                 .insert(AffectedByGravity)
                 .insert(RapierVelocity::zero())
-                .insert(Mass::default())
+                .insert(Mass {
+                    kilograms: stats.mass,
+                })
                 .insert(Velocity::default())
-                .insert(ActiveEvents::COLLISION_EVENTS);
+                .insert(ActiveEvents::COLLISION_EVENTS)
+                // Para que el contacto con los players, también cinemáticos, genere eventos
+                .insert(
+                    ActiveCollisionTypes::default() | ActiveCollisionTypes::KINEMATIC_KINEMATIC,
+                );
 
             // --- Aquí se añaden los componentes de IA según el tipo de enemigo ---
             match enemy_type {
@@ -141,16 +155,16 @@ pub fn spawn_enemies_characters(
                     enemy_entity
                         .insert(EnemyState::Patrolling)
                         .insert(Patrol {
-                            speed: 50.0,
+                            speed: stats.run_speed,
                             direction: 1,
                         })
-                        .insert(ContactDamage { amount: 1 });
+                        .insert(ContactDamage { amount: 2 }); // Medios corazones
                 }
                 EnemyType::Fufi => {
                     enemy_entity
                         .insert(EnemyState::Chasing)
                         .insert(Chase {
-                            speed: 80.0,
+                            speed: stats.run_speed,
                             range: 400.0,
                         })
                         .insert(RangedAttack {
@@ -180,5 +194,3 @@ pub fn spawn_enemies_characters(
         }
     }
 }
-// Puedes definir constantes aquí o en un submódulo de constantes si tienes muchas
-const ANIMATION_FPS: u8 = 10;
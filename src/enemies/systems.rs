@@ -1,18 +1,32 @@
 use bevy::prelude::*;
 use bevy_rapier2d::prelude::{
-    ActiveEvents, Collider, CollisionEvent, KinematicCharacterController, RigidBody,
+    ActiveEvents, Collider, ColliderDisabled, CollisionEvent, KinematicCharacterController,
+    KinematicCharacterControllerOutput, RigidBody,
 };
 use rand::Rng;
 
 use crate::{
-    enemies::components::{ContactDamage, EnemyCharacter, EnemyProjectile, EnemyState, Teleport},
+    animation::components::{AnimationState, CharacterAnimator, CharacterSprite},
+    cursor::components::{Projectile, WoolBall},
+    enemies::components::{
+        ContactDamage, EnemyAssets, EnemyCharacter, EnemyDefeated, EnemyHealth, EnemyHurt,
+        EnemyPoints, EnemyProjectile, EnemyState, Teleport,
+    },
     physics::{Mass, Velocity},
-    player::components::{
-        AnimationIndices, CharacterIdleSprite, CharacterLeftSprite, CharacterRightSprite, GRAVITY,
-        Health, Invincibility, PlayerCharacter,
+    player::components::{GRAVITY, Health, Invincibility, PlayerCharacter, PlayerHit},
+    pool::{
+        components::{EntityPool, PoolKind},
+        systems::spawn_hit_effect,
     },
+    save::components::RunStats,
 };
 
+// Daño en medios corazones: un proyectil quita un corazón entero
+const ENEMY_PROJECTILE_DAMAGE: u32 = 2;
+const ENEMY_HURT_TIME: f32 = 0.15;
+const ENEMY_HURT_COLOR: Color = Color::srgb(1.0, 0.4, 0.4);
+const ENEMY_DEFEAT_TIME: f32 = 0.8;
+
 pub fn gravity_system(
     time: Res<Time>,
     mut query: Query<(&mut Velocity, &Mass), With<EnemyCharacter>>,
@@ -23,23 +37,6 @@ pub fn gravity_system(
     }
 }
 
-pub fn execute_animations(time: Res<Time>, mut query: Query<(&mut AnimationIndices, &mut Sprite)>) {
-    for (mut config, mut sprite) in &mut query {
-        config.frame_timer.tick(time.delta());
-
-        if config.frame_timer.just_finished() {
-            if let Some(atlas) = &mut sprite.texture_atlas {
-                if atlas.index == config.last {
-                    atlas.index = config.first;
-                } else {
-                    atlas.index += 1;
-                    config.frame_timer = AnimationIndices::timer_from_fps(config.fps);
-                }
-            }
-        }
-    }
-}
-
 pub fn apply_velocity_to_controller(
     time: Res<Time>,
     mut query: Query<(&Velocity, &mut KinematicCharacterController), With<EnemyCharacter>>,
@@ -54,51 +51,150 @@ pub fn apply_velocity_to_controller(
     }
 }
 
-pub fn character_input_handling(
-    mut visibility_queries: ParamSet<(
-        Query<&mut Visibility, With<CharacterLeftSprite>>,
-        Query<&mut Visibility, With<CharacterRightSprite>>,
-        Query<&mut Visibility, With<CharacterIdleSprite>>,
-    )>,
+// Los enemigos andan o se quedan quietos según su velocidad, y caen si no tocan el suelo
+pub fn update_enemy_animation(
+    mut query: Query<
+        (
+            &mut CharacterAnimator,
+            &Velocity,
+            &KinematicCharacterControllerOutput,
+            Has<EnemyDefeated>,
+        ),
+        With<EnemyCharacter>,
+    >,
 ) {
-    let left = false;
-    let right = false;
-
-    match (left, right) {
-        (true, false) => {
-            // Moviendo a la izquierda
-            if let Ok(mut v) = visibility_queries.p0().single_mut() {
-                *v = Visibility::Visible;
-            }
-            if let Ok(mut v) = visibility_queries.p1().single_mut() {
-                *v = Visibility::Hidden;
-            }
-            if let Ok(mut v) = visibility_queries.p2().single_mut() {
-                *v = Visibility::Hidden;
-            }
+    for (mut animator, velocity, output, defeated) in &mut query {
+        // Los derrotados se quedan en su clip de muerte
+        if defeated {
+            continue;
         }
-        (false, true) => {
-            // Moviendo a la derecha
-            if let Ok(mut v) = visibility_queries.p0().single_mut() {
-                *v = Visibility::Hidden;
-            }
-            if let Ok(mut v) = visibility_queries.p1().single_mut() {
-                *v = Visibility::Visible;
+        let state = if !output.grounded {
+            AnimationState::Fall
+        } else if velocity.velocity.x.abs() > 1.0 {
+            AnimationState::Walk
+        } else {
+            AnimationState::Idle
+        };
+        animator.set_state(state);
+    }
+}
+
+// Una bola de lana que da a un enemigo le quita vida; al llegar a cero queda derrotado
+pub fn wool_ball_hit_enemy_system(
+    mut commands: Commands,
+    mut collision_events: EventReader<CollisionEvent>,
+    mut projectile_query: Query<(&mut Projectile, &Sprite), With<WoolBall>>,
+    mut enemy_query: Query<
+        (
+            &mut EnemyHealth,
+            &mut CharacterAnimator,
+            &EnemyPoints,
+            &GlobalTransform,
+        ),
+        Without<EnemyDefeated>,
+    >,
+    mut pool: ResMut<EntityPool>,
+    enemy_assets: Res<EnemyAssets>,
+    mut run_stats: ResMut<RunStats>,
+) {
+    for event in collision_events.read() {
+        let CollisionEvent::Started(entity1, entity2, _) = event else {
+            continue;
+        };
+        // El evento no garantiza el orden de las entidades
+        for (ball_entity, enemy_entity) in [(*entity1, *entity2), (*entity2, *entity1)] {
+            let Ok((mut projectile, ball_sprite)) = projectile_query.get_mut(ball_entity) else {
+                continue;
+            };
+            let Ok((mut health, mut animator, points, enemy_transform)) =
+                enemy_query.get_mut(enemy_entity)
+            else {
+                continue;
+            };
+            if projectile.damage == 0 {
+                continue;
             }
-            if let Ok(mut v) = visibility_queries.p2().single_mut() {
-                *v = Visibility::Hidden;
+
+            health.current = health.current.saturating_sub(projectile.damage);
+            projectile.damage = 0;
+            spawn_hit_effect(
+                &mut commands,
+                &mut pool,
+                enemy_transform.translation().xy(),
+                ball_sprite.image.clone(),
+            );
+
+            if health.current == 0 {
+                run_stats.score += points.0;
+                animator.set_state(AnimationState::Death);
+                commands
+                    .entity(enemy_entity)
+                    .insert((
+                        EnemyState::Dead,
+                        EnemyDefeated {
+                            timer: Timer::from_seconds(ENEMY_DEFEAT_TIME, TimerMode::Once),
+                        },
+                        ColliderDisabled,
+                    ))
+                    .remove::<ContactDamage>();
+                commands.spawn((
+                    AudioPlayer::new(enemy_assets.defeat_sound.clone()),
+                    PlaybackSettings::DESPAWN,
+                ));
+            } else {
+                animator.play_once(AnimationState::Hurt);
+                commands.entity(enemy_entity).insert(EnemyHurt {
+                    timer: Timer::from_seconds(ENEMY_HURT_TIME, TimerMode::Once),
+                });
+                commands.spawn((
+                    AudioPlayer::new(enemy_assets.hurt_sound.clone()),
+                    PlaybackSettings::DESPAWN,
+                ));
             }
         }
-        _ => {
-            // Sin movimiento o ambas teclas presionadas - mostrar idle
-            if let Ok(mut v) = visibility_queries.p0().single_mut() {
-                *v = Visibility::Hidden;
-            }
-            if let Ok(mut v) = visibility_queries.p1().single_mut() {
-                *v = Visibility::Hidden;
+    }
+}
+
+// Tiñe de rojo a los enemigos recién golpeados y quita el destello al acabar
+pub fn enemy_hurt_flash_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut enemy_query: Query<(Entity, &mut EnemyHurt, &Children), Without<EnemyDefeated>>,
+    mut sprite_query: Query<&mut Sprite, With<CharacterSprite>>,
+) {
+    for (entity, mut hurt, children) in &mut enemy_query {
+        hurt.timer.tick(time.delta());
+        let color = if hurt.timer.finished() {
+            commands.entity(entity).remove::<EnemyHurt>();
+            Color::WHITE
+        } else {
+            ENEMY_HURT_COLOR
+        };
+        for child in children.iter() {
+            if let Ok(mut sprite) = sprite_query.get_mut(child) {
+                sprite.color = color;
             }
-            if let Ok(mut v) = visibility_queries.p2().single_mut() {
-                *v = Visibility::Visible;
+        }
+    }
+}
+
+// Los enemigos derrotados se desvanecen y desaparecen
+pub fn enemy_defeat_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut enemy_query: Query<(Entity, &mut EnemyDefeated, &Children)>,
+    mut sprite_query: Query<&mut Sprite, With<CharacterSprite>>,
+) {
+    for (entity, mut defeated, children) in &mut enemy_query {
+        defeated.timer.tick(time.delta());
+        if defeated.timer.finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        let alpha = 1.0 - defeated.timer.fraction();
+        for child in children.iter() {
+            if let Ok(mut sprite) = sprite_query.get_mut(child) {
+                sprite.color = Color::WHITE.with_alpha(alpha);
             }
         }
     }
@@ -125,69 +221,107 @@ pub fn teleport_system(
 pub fn enemy_damage_system(
     mut commands: Commands,
     mut collision_events: EventReader<CollisionEvent>,
-    mut player_query: Query<(Entity, &mut Health, Option<&Invincibility>), With<PlayerCharacter>>,
+    mut player_query: Query<(&mut Health, Option<&Invincibility>), With<PlayerCharacter>>,
     contact_damage_query: Query<&ContactDamage>,
-    projectile_query: Query<Entity, With<EnemyProjectile>>,
+    transform_query: Query<(&GlobalTransform, Has<EnemyProjectile>)>,
+    mut pool: ResMut<EntityPool>,
+    mut hit_events: EventWriter<PlayerHit>,
 ) {
-    let Ok((player_entity, mut player_health, invincibility)) = player_query.single_mut() else {
-        return;
-    };
-
-    // Si el jugador es invencible, no procesar daño.
-    if invincibility.is_some() {
-        return;
-    }
-
     for event in collision_events.read() {
-        if let CollisionEvent::Started(entity1, entity2, _) = event {
-            let (other_entity, is_player_involved) = if *entity1 == player_entity {
-                (*entity2, true)
-            } else if *entity2 == player_entity {
-                (*entity1, true)
-            } else {
-                (Entity::PLACEHOLDER, false)
+        let CollisionEvent::Started(entity1, entity2, _) = event else {
+            continue;
+        };
+
+        // Cualquiera de las dos entidades puede ser un player
+        for (player_entity, other_entity) in [(*entity1, *entity2), (*entity2, *entity1)] {
+            let Ok((mut player_health, invincibility)) = player_query.get_mut(player_entity) else {
+                continue;
             };
 
-            if is_player_involved {
-                // Daño por contacto directo
-                if let Ok(contact_damage) = contact_damage_query.get(other_entity) {
-                    player_health.current =
-                        player_health.current.saturating_sub(contact_damage.amount);
-                    // Activar invencibilidad para el jugador
-                    commands
-                        .entity(player_entity)
-                        .insert(Invincibility::new(1.5));
-                }
+            // Si el jugador es invencible, no procesar daño.
+            if invincibility.is_some() {
+                continue;
+            }
 
-                // Daño por proyectil
-                if projectile_query.get(other_entity).is_ok() {
-                    player_health.current = player_health.current.saturating_sub(1);
-                    commands.entity(other_entity).despawn(); // Despawn proyectil
-                    // Activar invencibilidad
-                    commands
-                        .entity(player_entity)
-                        .insert(Invincibility::new(1.5));
+            // El empujón aleja al player del enemigo o del proyectil que le ha dado
+            let direction = match (
+                transform_query.get(player_entity),
+                transform_query.get(other_entity),
+            ) {
+                (Ok((player, _)), Ok((source, _))) => {
+                    (player.translation() - source.translation()).xy()
                 }
+                _ => Vec2::ZERO,
+            };
+            let hit = PlayerHit {
+                player: player_entity,
+                direction,
+            };
+
+            // Daño por contacto directo
+            if let Ok(contact_damage) = contact_damage_query.get(other_entity) {
+                player_health.current = player_health.current.saturating_sub(contact_damage.amount);
+                // Activar invencibilidad para el jugador
+                commands
+                    .entity(player_entity)
+                    .insert(Invincibility::new(1.5));
+                hit_events.write(hit);
+                continue;
+            }
+
+            // Daño por proyectil
+            if transform_query
+                .get(other_entity)
+                .is_ok_and(|(_, is_projectile)| is_projectile)
+            {
+                player_health.current = player_health
+                    .current
+                    .saturating_sub(ENEMY_PROJECTILE_DAMAGE);
+                pool.release(&mut commands, PoolKind::EnemyProjectile, other_entity);
+                // Activar invencibilidad
+                commands
+                    .entity(player_entity)
+                    .insert(Invincibility::new(1.5));
+                hit_events.write(hit);
             }
         }
     }
 }
 
-/// Spawnea un proyectil.
-fn spawn_projectile(commands: &mut Commands, position: Vec3, direction: Vec2) {
-    commands.spawn((
-        Sprite {
-            color: Color::srgb(0.9, 0.1, 0.1),
-            custom_size: Some(Vec2::splat(10.0)),
-            ..default()
-        },
+/// Spawnea un proyectil, reutilizando uno del pool si hay.
+fn spawn_projectile(
+    commands: &mut Commands,
+    pool: &mut EntityPool,
+    position: Vec3,
+    direction: Vec2,
+) {
+    let entity = pool
+        .reuse(commands, PoolKind::EnemyProjectile)
+        .unwrap_or_else(|| {
+            let entity = commands
+                .spawn((
+                    Sprite {
+                        color: Color::srgb(0.9, 0.1, 0.1),
+                        custom_size: Some(Vec2::splat(10.0)),
+                        ..default()
+                    },
+                    EnemyProjectile,
+                    RigidBody::Dynamic,
+                    Collider::ball(5.0),
+                    ActiveEvents::COLLISION_EVENTS,
+                ))
+                .id();
+            pool.track(PoolKind::EnemyProjectile, entity);
+            entity
+        });
+    commands.entity(entity).insert((
         Transform::from_translation(position),
-        EnemyProjectile,
         Velocity {
             velocity: direction * 400.0,
         }, // Velocidad del proyectil
-        RigidBody::Dynamic,
-        Collider::ball(5.0),
-        ActiveEvents::COLLISION_EVENTS,
     ));
 }
+
+pub fn release_enemy_projectiles(mut commands: Commands, mut pool: ResMut<EntityPool>) {
+    pool.release_all(&mut commands, PoolKind::EnemyProjectile);
+}
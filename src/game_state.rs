@@ -1,12 +1,26 @@
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Copy, Default, Eq, PartialEq, Debug, Hash, States)]
 pub enum GameState {
     #[default]
     MainMenu,
+    CharacterSelect,
     Game,
     PauseMenu,
     GameOver,
+    LevelComplete,
+}
+
+/// Condición: la transición actual de `GameState` entra o sale del menú de pausa.
+///
+/// Los sistemas de `OnEnter(GameState::Game)` y `OnExit(GameState::Game)` la usan para no
+/// respawnear ni despawnear el nivel al pausar y reanudar.
+pub fn is_pause_transition(mut transitions: EventReader<StateTransitionEvent<GameState>>) -> bool {
+    transitions.read().last().is_some_and(|transition| {
+        transition.exited == Some(GameState::PauseMenu)
+            || transition.entered == Some(GameState::PauseMenu)
+    })
 }
 
 #[derive(Clone, Copy, Default, Eq, PartialEq, Debug, Hash, States)]
@@ -16,7 +30,7 @@ pub enum LevelState {
     Loading,
     LevelLoaded,
 }
-#[derive(Resource, Debug, PartialEq, Eq, Clone, Copy, Hash)]
+#[derive(Resource, Debug, PartialEq, Eq, Clone, Copy, Hash, Serialize, Deserialize)]
 pub enum Level {
     Level1,
 }
@@ -24,6 +38,7 @@ pub enum Level {
 pub struct LevelPaths {
     pub config: String,
     pub player: String,
+    pub events: String,
     pub tiles: String,
     pub background: Vec<String>,
 }
@@ -32,12 +47,14 @@ impl LevelPaths {
     pub fn new(
         config: &'static str,
         player: &'static str,
+        events: &'static str,
         tiles: &'static str,
         background: Vec<&'static str>,
     ) -> Self {
         Self {
             config: config.to_string(),
             player: player.to_string(),
+            events: events.to_string(),
             tiles: tiles.to_string(),
             background: background.iter().map(|&s| s.to_string()).collect(),
         }
@@ -50,6 +67,7 @@ impl Level {
             Level::Level1 => LevelPaths::new(
                 "assets/levels/level1/level1.json",
                 "assets/levels/level1/level1_hero.json",
+                "assets/levels/level1/level1_events.json",
                 "levels/level1/level1.png",
                 vec![
                     "levels/level1/background/1.png",
@@ -0,0 +1,305 @@
+use std::collections::{HashMap, HashSet};
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::player::components::{MAX_PLAYERS, PlayerId};
+
+/// Acciones de juego. Los sistemas leen acciones en lugar de teclas concretas.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Action {
+    MoveLeft,
+    MoveRight,
+    MoveDown,
+    Jump,
+    Throw,
+    ToggleArc,
+    AimUp,
+    AimDown,
+    ToggleLockOn,
+    Dash,
+    Pause,
+    ToggleSplits,
+    MenuUp,
+    MenuDown,
+    MenuConfirm,
+}
+
+impl Action {
+    pub const ALL: [Action; 15] = [
+        Action::MoveLeft,
+        Action::MoveRight,
+        Action::MoveDown,
+        Action::Jump,
+        Action::Throw,
+        Action::ToggleArc,
+        Action::AimUp,
+        Action::AimDown,
+        Action::ToggleLockOn,
+        Action::Dash,
+        Action::Pause,
+        Action::ToggleSplits,
+        Action::MenuUp,
+        Action::MenuDown,
+        Action::MenuConfirm,
+    ];
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Binding {
+    Key(KeyCode),
+    Mouse(MouseButton),
+    GamepadButton(GamepadButton),
+    // Eje del mando y sentido (1.0 o -1.0) en el que cuenta como pulsado
+    GamepadAxis(GamepadAxis, f32),
+}
+
+/// Asignación de controles, cargada y guardada en el fichero de configuración.
+#[derive(Resource, Debug, Clone, Serialize, Deserialize)]
+pub struct InputBindings {
+    pub bindings: HashMap<Action, Vec<Binding>>,
+}
+
+impl Default for InputBindings {
+    fn default() -> Self {
+        let bindings = HashMap::from([
+            (
+                Action::MoveLeft,
+                vec![
+                    Binding::Key(KeyCode::ArrowLeft),
+                    Binding::Key(KeyCode::KeyA),
+                    Binding::GamepadButton(GamepadButton::DPadLeft),
+                    Binding::GamepadAxis(GamepadAxis::LeftStickX, -1.0),
+                ],
+            ),
+            (
+                Action::MoveRight,
+                vec![
+                    Binding::Key(KeyCode::ArrowRight),
+                    Binding::Key(KeyCode::KeyD),
+                    Binding::GamepadButton(GamepadButton::DPadRight),
+                    Binding::GamepadAxis(GamepadAxis::LeftStickX, 1.0),
+                ],
+            ),
+            // Abajo sobre una plataforma de un sentido la atraviesa
+            (
+                Action::MoveDown,
+                vec![
+                    Binding::Key(KeyCode::ArrowDown),
+                    Binding::Key(KeyCode::KeyS),
+                    Binding::GamepadButton(GamepadButton::DPadDown),
+                    Binding::GamepadAxis(GamepadAxis::LeftStickY, -1.0),
+                ],
+            ),
+            (
+                Action::Jump,
+                vec![
+                    Binding::Key(KeyCode::ArrowUp),
+                    Binding::Key(KeyCode::KeyW),
+                    Binding::GamepadButton(GamepadButton::South),
+                ],
+            ),
+            (
+                Action::Throw,
+                vec![
+                    Binding::Mouse(MouseButton::Left),
+                    Binding::Key(KeyCode::KeyE),
+                    Binding::GamepadButton(GamepadButton::RightTrigger2),
+                ],
+            ),
+            (
+                Action::ToggleArc,
+                vec![
+                    Binding::Key(KeyCode::KeyQ),
+                    Binding::Mouse(MouseButton::Right),
+                    Binding::GamepadButton(GamepadButton::North),
+                ],
+            ),
+            // Apuntar sin ratón: R y F giran el puntero, T fija a los enemigos
+            (Action::AimUp, vec![Binding::Key(KeyCode::KeyR)]),
+            (Action::AimDown, vec![Binding::Key(KeyCode::KeyF)]),
+            (
+                Action::ToggleLockOn,
+                vec![
+                    Binding::Key(KeyCode::KeyT),
+                    Binding::GamepadButton(GamepadButton::RightThumb),
+                ],
+            ),
+            (
+                Action::Dash,
+                vec![
+                    Binding::Key(KeyCode::ShiftLeft),
+                    Binding::Key(KeyCode::KeyK),
+                    Binding::GamepadButton(GamepadButton::East),
+                ],
+            ),
+            (
+                Action::Pause,
+                vec![
+                    Binding::Key(KeyCode::Escape),
+                    Binding::GamepadButton(GamepadButton::Start),
+                ],
+            ),
+            (
+                Action::ToggleSplits,
+                vec![
+                    Binding::Key(KeyCode::F1),
+                    Binding::GamepadButton(GamepadButton::Select),
+                ],
+            ),
+            // Navegación de los menús sin ratón: cambiar de botón y pulsarlo
+            (
+                Action::MenuUp,
+                vec![
+                    Binding::Key(KeyCode::ArrowUp),
+                    Binding::Key(KeyCode::ArrowLeft),
+                    Binding::Key(KeyCode::KeyW),
+                    Binding::Key(KeyCode::KeyA),
+                    Binding::GamepadButton(GamepadButton::DPadUp),
+                    Binding::GamepadButton(GamepadButton::DPadLeft),
+                    Binding::GamepadAxis(GamepadAxis::LeftStickY, 1.0),
+                ],
+            ),
+            (
+                Action::MenuDown,
+                vec![
+                    Binding::Key(KeyCode::ArrowDown),
+                    Binding::Key(KeyCode::ArrowRight),
+                    Binding::Key(KeyCode::KeyS),
+                    Binding::Key(KeyCode::KeyD),
+                    Binding::GamepadButton(GamepadButton::DPadDown),
+                    Binding::GamepadButton(GamepadButton::DPadRight),
+                    Binding::GamepadAxis(GamepadAxis::LeftStickY, -1.0),
+                ],
+            ),
+            (
+                Action::MenuConfirm,
+                vec![
+                    Binding::Key(KeyCode::Enter),
+                    Binding::Key(KeyCode::Space),
+                    Binding::GamepadButton(GamepadButton::South),
+                ],
+            ),
+        ]);
+
+        Self { bindings }
+    }
+}
+
+impl Binding {
+    pub fn is_gamepad(&self) -> bool {
+        matches!(self, Binding::GamepadButton(_) | Binding::GamepadAxis(..))
+    }
+}
+
+impl InputBindings {
+    pub fn get(&self, action: Action) -> &[Binding] {
+        self.bindings.get(&action).map_or(&[], Vec::as_slice)
+    }
+}
+
+/// Estado de las acciones en el frame actual, resuelto a partir de `InputBindings`.
+#[derive(Resource, Debug, Default)]
+pub struct ActionState {
+    pressed: HashSet<Action>,
+    just_pressed: HashSet<Action>,
+    just_released: HashSet<Action>,
+}
+
+impl ActionState {
+    pub fn pressed(&self, action: Action) -> bool {
+        self.pressed.contains(&action)
+    }
+
+    pub fn just_pressed(&self, action: Action) -> bool {
+        self.just_pressed.contains(&action)
+    }
+
+    pub fn just_released(&self, action: Action) -> bool {
+        self.just_released.contains(&action)
+    }
+
+    pub fn update(&mut self, action: Action, pressed: bool) {
+        let was_pressed = self.pressed.contains(&action);
+
+        if pressed && !was_pressed {
+            self.just_pressed.insert(action);
+        }
+
+        if !pressed && was_pressed {
+            self.just_released.insert(action);
+        }
+
+        if pressed {
+            self.pressed.insert(action);
+        } else {
+            self.pressed.remove(&action);
+        }
+    }
+
+    pub fn clear_transitions(&mut self) {
+        self.just_pressed.clear();
+        self.just_released.clear();
+    }
+}
+
+/// Estado de las acciones de cada jugador local, indexado por `PlayerId`.
+///
+/// En solitario el jugador 1 usa todos los dispositivos; en co-op el jugador 1 juega con
+/// teclado y ratón y el jugador 2 con mando. `ActionState` sigue juntando todos los
+/// dispositivos para los menús y los atajos globales.
+#[derive(Resource, Debug, Default)]
+pub struct PlayerActions([ActionState; MAX_PLAYERS]);
+
+impl PlayerActions {
+    pub fn get(&self, player: &PlayerId) -> &ActionState {
+        &self.0[player.0.min(MAX_PLAYERS - 1)]
+    }
+
+    pub fn get_mut(&mut self, player: usize) -> &mut ActionState {
+        &mut self.0[player]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transitions_last_one_frame() {
+        let mut state = ActionState::default();
+
+        state.update(Action::Jump, true);
+        assert!(state.pressed(Action::Jump));
+        assert!(state.just_pressed(Action::Jump));
+        assert!(!state.just_released(Action::Jump));
+
+        // Mantener pulsado no vuelve a contar como pulsación nueva
+        state.clear_transitions();
+        state.update(Action::Jump, true);
+        assert!(state.pressed(Action::Jump));
+        assert!(!state.just_pressed(Action::Jump));
+
+        state.clear_transitions();
+        state.update(Action::Jump, false);
+        assert!(!state.pressed(Action::Jump));
+        assert!(state.just_released(Action::Jump));
+
+        state.clear_transitions();
+        state.update(Action::Jump, false);
+        assert!(!state.just_released(Action::Jump));
+    }
+
+    #[test]
+    fn actions_are_independent() {
+        let mut state = ActionState::default();
+
+        state.update(Action::Jump, true);
+        state.update(Action::MoveLeft, false);
+
+        assert!(state.just_pressed(Action::Jump));
+        assert!(!state.pressed(Action::MoveLeft));
+        assert!(!state.just_pressed(Action::MoveLeft));
+        assert!(!state.just_released(Action::MoveLeft));
+    }
+}
@@ -0,0 +1,32 @@
+pub mod components;
+pub mod systems;
+
+use std::path::PathBuf;
+
+use bevy::{input::InputSystem, prelude::*};
+
+use crate::{
+    input::{
+        components::{ActionState, PlayerActions},
+        systems::*,
+    },
+    save::data_dir,
+};
+
+const BINDINGS_FILE_NAME: &str = "bindings.json";
+
+// Ruta del fichero de configuración de controles
+#[derive(Resource, Debug, Clone)]
+pub struct BindingsPath(pub PathBuf);
+
+pub struct InputActionsPlugin;
+
+impl Plugin for InputActionsPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(BindingsPath(data_dir().join(BINDINGS_FILE_NAME)))
+            .init_resource::<ActionState>()
+            .init_resource::<PlayerActions>()
+            .add_systems(PreStartup, load_input_bindings)
+            .add_systems(PreUpdate, update_action_state.after(InputSystem));
+    }
+}
@@ -0,0 +1,101 @@
+use std::fs;
+
+use bevy::prelude::*;
+
+use crate::{
+    input::{
+        BindingsPath,
+        components::{Action, ActionState, Binding, InputBindings, PlayerActions},
+    },
+    player::components::{MAX_PLAYERS, PlayerCount},
+};
+
+const GAMEPAD_AXIS_THRESHOLD: f32 = 0.5;
+
+// Carga los controles del fichero de configuración; si no existe, lo crea con los de por defecto
+pub fn load_input_bindings(mut commands: Commands, bindings_path: Res<BindingsPath>) {
+    let mut bindings = match fs::read_to_string(&bindings_path.0) {
+        Ok(json) => serde_json::from_str(&json).unwrap_or_else(|err| {
+            warn!("Failed to parse bindings file {:?}: {err}", bindings_path.0);
+            InputBindings::default()
+        }),
+        Err(_) => {
+            let bindings = InputBindings::default();
+            save_input_bindings(&bindings_path, &bindings);
+            bindings
+        }
+    };
+
+    // Las acciones que no estén en un fichero antiguo usan los controles por defecto
+    for (action, defaults) in InputBindings::default().bindings {
+        bindings.bindings.entry(action).or_insert(defaults);
+    }
+
+    commands.insert_resource(bindings);
+}
+
+pub fn save_input_bindings(bindings_path: &BindingsPath, bindings: &InputBindings) {
+    if let Some(parent) = bindings_path.0.parent()
+        && let Err(err) = fs::create_dir_all(parent)
+    {
+        warn!("Failed to create config directory {:?}: {err}", parent);
+        return;
+    }
+
+    match serde_json::to_string_pretty(bindings) {
+        Ok(json) => {
+            if let Err(err) = fs::write(&bindings_path.0, json) {
+                warn!("Failed to write bindings file {:?}: {err}", bindings_path.0);
+            }
+        }
+        Err(err) => warn!("Failed to serialize bindings: {err}"),
+    }
+}
+
+// Traduce el estado de teclado, ratón y mandos a acciones, globales y por jugador
+pub fn update_action_state(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    gamepads: Query<&Gamepad>,
+    bindings: Res<InputBindings>,
+    player_count: Res<PlayerCount>,
+    mut action_state: ResMut<ActionState>,
+    mut player_actions: ResMut<PlayerActions>,
+) {
+    action_state.clear_transitions();
+    for player in 0..MAX_PLAYERS {
+        player_actions.get_mut(player).clear_transitions();
+    }
+
+    for action in Action::ALL {
+        let mut keyboard_pressed = false;
+        let mut gamepad_pressed = false;
+        for binding in bindings.get(action) {
+            let pressed = match binding {
+                Binding::Key(key) => keyboard.pressed(*key),
+                Binding::Mouse(button) => mouse.pressed(*button),
+                Binding::GamepadButton(button) => {
+                    gamepads.iter().any(|gamepad| gamepad.pressed(*button))
+                }
+                Binding::GamepadAxis(axis, direction) => gamepads.iter().any(|gamepad| {
+                    gamepad.get(*axis).unwrap_or(0.0) * direction > GAMEPAD_AXIS_THRESHOLD
+                }),
+            };
+            if binding.is_gamepad() {
+                gamepad_pressed |= pressed;
+            } else {
+                keyboard_pressed |= pressed;
+            }
+        }
+
+        let any_pressed = keyboard_pressed || gamepad_pressed;
+        action_state.update(action, any_pressed);
+        if player_count.is_coop() {
+            player_actions.get_mut(0).update(action, keyboard_pressed);
+            player_actions.get_mut(1).update(action, gamepad_pressed);
+        } else {
+            player_actions.get_mut(0).update(action, any_pressed);
+            player_actions.get_mut(1).update(action, false);
+        }
+    }
+}
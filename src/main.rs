@@ -1,16 +1,32 @@
+mod animation;
+mod characters;
+mod checkpoints;
+mod collectibles;
 mod cursor;
 mod enemies;
 mod game_state;
+mod input;
 mod map;
 mod menu;
 mod parallax;
 mod physics;
 mod player;
+mod pool;
+mod save;
+mod speedrun;
+use crate::animation::CharacterAnimationPlugin;
+use crate::characters::CharacterStatsPlugin;
+use crate::checkpoints::CheckpointsPlugin;
+use crate::collectibles::CollectiblesPlugin;
 use crate::cursor::CursorPlugin;
 use crate::enemies::EnemiesPlugin;
+use crate::input::InputActionsPlugin;
 // use crate::enemies::EnemiesPlugin;
 use crate::physics::{gravity_system, kinematic_character_movement_system};
 use crate::player::PlayerPlugin;
+use crate::pool::PoolPlugin;
+use crate::save::SavePlugin;
+use crate::speedrun::SpeedrunPlugin;
 use crate::{menu::MenuPlugin, parallax::components::MainCamera};
 // use crate::player::PlayerPlugin;
 use crate::{map::MapPlugin, parallax::systems::camera_follow_system};
@@ -31,6 +47,7 @@ fn main() {
         .add_plugins(RapierPhysicsPlugin::<NoUserData>::pixels_per_meter(32.0))
         .add_plugins(RapierDebugRenderPlugin::default())
         .init_state::<GameState>()
+        .add_plugins(InputActionsPlugin)
         .add_plugins(MenuPlugin)
         .add_plugins(CursorPlugin)
         // --- Sistemas de Estado ---
@@ -39,8 +56,15 @@ fn main() {
         // .add_systems(Update, systems::menu_input_handling.run_if(in_state(GameState::MainMenu)))
         // .add_systems(OnExit(GameState::MainMenu), systems::despawn_all_entities)
         .add_plugins(MapPlugin)
+        .add_plugins(CharacterStatsPlugin)
         .add_plugins(PlayerPlugin)
+        .add_plugins(CharacterAnimationPlugin)
         .add_plugins(EnemiesPlugin)
+        .add_plugins(CollectiblesPlugin)
+        .add_plugins(CheckpointsPlugin)
+        .add_plugins(SavePlugin)
+        .add_plugins(SpeedrunPlugin)
+        .add_plugins(PoolPlugin)
         .add_systems(Startup, setup_camera_and_ui)
         .add_systems(
             Update,
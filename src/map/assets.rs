@@ -3,7 +3,7 @@ use bevy::prelude::*;
 use serde_json; //
 use std::fs;
 
-use crate::map::components::{CurrentLevelInfo, LevelData}; //
+use crate::map::components::{CurrentLevelInfo, LevelData, LevelEvents}; //
 // Recurso para almacenar los handles del atlas y la textura del tilemap, y el tamaño del tile
 #[derive(Resource)]
 pub struct GameAssets {
@@ -26,6 +26,11 @@ pub fn load_map_assets(
     )
     .expect("Failed to parse level JSON");
 
+    let level_events: LevelEvents = serde_json::from_str(
+        &fs::read_to_string(level_info.data.events.clone()).expect("Failed to read events JSON"),
+    )
+    .expect("Failed to parse events JSON");
+
     let tile_size_from_json = level_data.tile_size as f32;
     let map_width_from_json = level_data.map_width as u32;
     let map_height_from_json = level_data.map_height as u32;
@@ -38,6 +43,7 @@ pub fn load_map_assets(
         .collect();
 
     commands.insert_resource(level_data);
+    commands.insert_resource(level_events);
     commands.insert_resource(GameAssets {
         tile_texture: tile_texture_handle.clone(),
         parallax_backgrounds: parallax_bg.clone(),
@@ -46,3 +52,18 @@ pub fn load_map_assets(
         map_height_tiles: map_height_from_json,
     });
 }
+
+impl GameAssets {
+    // Convierte una posición en tiles (origen arriba a la izquierda) al centro del tile en el mundo
+    pub fn tile_to_world(&self, x: f32, y: f32) -> Vec2 {
+        let world_x =
+            x * self.tile_size_px - (self.map_width_tiles as f32 * self.tile_size_px / 2.0);
+        let world_y =
+            -y * self.tile_size_px + (self.map_height_tiles as f32 * self.tile_size_px / 2.0);
+
+        Vec2::new(
+            world_x + self.tile_size_px / 2.0,
+            world_y - self.tile_size_px / 2.0,
+        )
+    }
+}
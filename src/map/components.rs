@@ -3,7 +3,10 @@
 use bevy::prelude::*;
 use serde::Deserialize;
 
-use crate::game_state::LevelPaths;
+use crate::{
+    game_state::{Level, LevelPaths},
+    player::components::HALF_HEARTS_PER_HEART,
+};
 
 // Estructuras para deserializar el JSON del nivel
 #[derive(Debug, Deserialize, Resource)] // Añadimos Resource aquí
@@ -28,6 +31,19 @@ pub struct TilePosition {
     pub id: u32,
 }
 
+// Eventos del nivel (objetos colocados sobre el mapa: coleccionables, fin de nivel...)
+#[derive(Debug, Deserialize, Resource)]
+pub struct LevelEvents {
+    pub events: Vec<EventData>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EventData {
+    pub path: String,
+    pub x: u32,
+    pub y: u32,
+}
+
 // Componente marcador para los tiles del nivel
 #[derive(Component)]
 pub struct LevelTile;
@@ -47,9 +63,11 @@ pub enum TileType {
 // Enum para objetos pasivos coleccionables
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum CollectibleType {
-    Heart,      // Corazón que recupera vida
-    YarnBall,   // Ovillo de lana que da puntos
-    BoneCookie, // Galleta de hueso que aplica boost
+    Heart,          // Corazón que recupera vida
+    YarnBall,       // Ovillo de lana que da puntos y recarga lana
+    BoneCookie,     // Galleta de hueso que aplica boost
+    OneUp,          // Vida extra
+    HeartContainer, // Contenedor de corazón que sube la vida máxima
 }
 
 // Propiedades para tiles
@@ -149,10 +167,13 @@ impl TileProperties {
 #[derive(Component, Debug, Clone)]
 pub struct CollectibleProperties {
     pub collectible_type: CollectibleType,
-    pub health_restore: i32,   // Cantidad de vida que restaura
-    pub points_value: i32,     // Puntos que otorga
-    pub boost_duration: f32,   // Duración del boost en segundos
-    pub boost_multiplier: f32, // Multiplicador del boost
+    pub health_restore: i32,      // Medios corazones que restaura
+    pub points_value: i32,        // Puntos que otorga
+    pub boost_duration: f32,      // Duración del boost en segundos
+    pub boost_multiplier: f32,    // Multiplicador del boost
+    pub extra_lives: u32,         // Vidas extra que otorga
+    pub max_health_increase: u32, // Medios corazones que suma a la vida máxima
+    pub ammo_restore: u32,        // Bolas de lana que recarga
 }
 
 impl CollectibleProperties {
@@ -163,16 +184,22 @@ impl CollectibleProperties {
             points_value: 0,
             boost_duration: 0.0,
             boost_multiplier: 1.0,
+            extra_lives: 0,
+            max_health_increase: 0,
+            ammo_restore: 0,
         }
     }
 
-    pub fn yarn_ball(points: i32) -> Self {
+    pub fn yarn_ball(points: i32, ammo: u32) -> Self {
         CollectibleProperties {
             collectible_type: CollectibleType::YarnBall,
             health_restore: 0,
             points_value: points,
             boost_duration: 0.0,
             boost_multiplier: 1.0,
+            extra_lives: 0,
+            max_health_increase: 0,
+            ammo_restore: ammo,
         }
     }
 
@@ -183,6 +210,35 @@ impl CollectibleProperties {
             points_value: 0,
             boost_duration: duration,
             boost_multiplier: multiplier,
+            extra_lives: 0,
+            max_health_increase: 0,
+            ammo_restore: 0,
+        }
+    }
+
+    pub fn one_up(lives: u32) -> Self {
+        CollectibleProperties {
+            collectible_type: CollectibleType::OneUp,
+            health_restore: 0,
+            points_value: 0,
+            boost_duration: 0.0,
+            boost_multiplier: 1.0,
+            extra_lives: lives,
+            max_health_increase: 0,
+            ammo_restore: 0,
+        }
+    }
+
+    pub fn heart_container(max_health_increase: u32) -> Self {
+        CollectibleProperties {
+            collectible_type: CollectibleType::HeartContainer,
+            health_restore: 0,
+            points_value: 0,
+            boost_duration: 0.0,
+            boost_multiplier: 1.0,
+            extra_lives: 0,
+            max_health_increase,
+            ammo_restore: 0,
         }
     }
 }
@@ -192,7 +248,8 @@ pub fn get_tile_properties_from_path(path: &str) -> Option<TileProperties> {
     match path {
         "solid" | "ground" | "box" => Some(TileProperties::solid()),
         "falling" | "falling_platform" => Some(TileProperties::falling()),
-        "damage" | "spikes" | "hurt" => Some(TileProperties::damage(1)),
+        // El daño va en medios corazones
+        "damage" | "spikes" | "hurt" => Some(TileProperties::damage(2)),
         "pipe_left" | "pipe_bottom_left" => Some(TileProperties::pipe_bottom_left()),
         "pipe_right" | "pipe_bottom_right" => Some(TileProperties::pipe_bottom_right()),
         "bouncy" | "bouncy_platform" | "moving_platform" => Some(TileProperties::bouncy()),
@@ -201,6 +258,20 @@ pub fn get_tile_properties_from_path(path: &str) -> Option<TileProperties> {
     }
 }
 
+// Mapeo basado en el path del evento a propiedades de coleccionables
+pub fn get_collectible_properties_from_path(path: &str) -> Option<CollectibleProperties> {
+    match path {
+        "Heart" | "heart" => Some(CollectibleProperties::heart(2)),
+        "YarnBall" | "yarn_ball" => Some(CollectibleProperties::yarn_ball(10, 3)),
+        "BoneCookie" | "bone_cookie" => Some(CollectibleProperties::bone_cookie(5.0, 1.5)),
+        "OneUp" | "one_up" => Some(CollectibleProperties::one_up(1)),
+        "HeartContainer" | "heart_container" => Some(CollectibleProperties::heart_container(
+            HALF_HEARTS_PER_HEART,
+        )),
+        _ => None,
+    }
+}
+
 // Componente para tiles que caen
 #[derive(Component, Debug)]
 pub struct FallingTile {
@@ -232,6 +303,13 @@ impl Default for FallingTile {
     }
 }
 
+/// El player está atravesando las plataformas de un sentido tras pulsar abajo encima
+/// de una. Mientras dure no choca con ninguna.
+#[derive(Component, Debug)]
+pub struct DropThrough {
+    pub timer: Timer,
+}
+
 // Componente para objetos coleccionables
 #[derive(Component, Debug)]
 pub struct CollectibleItem {
@@ -239,15 +317,17 @@ pub struct CollectibleItem {
     pub collected: bool,
     pub bob_timer: Timer,   // Para animación de flotación
     pub bob_amplitude: f32, // Amplitud del movimiento de flotación
+    pub original_position: Vec3,
 }
 
 impl Default for CollectibleItem {
     fn default() -> Self {
         CollectibleItem {
-            properties: CollectibleProperties::yarn_ball(10),
+            properties: CollectibleProperties::yarn_ball(10, 3),
             collected: false,
             bob_timer: Timer::from_seconds(2.0, TimerMode::Repeating),
             bob_amplitude: 5.0,
+            original_position: Vec3::ZERO,
         }
     }
 }
@@ -288,5 +368,6 @@ impl Default for BouncyPlatform {
 
 #[derive(Resource)]
 pub struct CurrentLevelInfo {
+    pub level: Level,
     pub data: LevelPaths,
 }
@@ -5,14 +5,16 @@ pub mod tile_systems;
 use bevy::prelude::*;
 
 // Importar los recursos y componentes necesarios
-use crate::game_state::{GameState, Level, LevelState};
+use crate::game_state::{GameState, Level, LevelState, is_pause_transition};
+use crate::input::components::{Action, PlayerActions};
 use crate::parallax::components::ParallaxLayer;
 use crate::physics::Velocity as PlayerVelocity;
 use crate::player::PLAYER_GROUP;
-use crate::player::components::PlayerCharacter;
+use crate::player::components::{PlayerCharacter, PlayerId};
 use assets::GameAssets;
 use bevy_rapier2d::prelude::{
-    Collider, CollisionGroups, Group, KinematicCharacterController, RigidBody,
+    Collider, CollisionGroups, Group, KinematicCharacterController,
+    KinematicCharacterControllerOutput, RigidBody,
 };
 use components::LevelData;
 // Agregar componentes específicos según el tipo de tile
@@ -21,8 +23,8 @@ use crate::{
     map::{
         assets::load_map_assets,
         components::{
-            BouncyPlatform, ColliderShape, DamageTile, FallingTile, LevelTile, PipeTile,
-            TileProperties, get_tile_properties_from_path,
+            BouncyPlatform, ColliderShape, DamageTile, DropThrough, FallingTile, LevelTile,
+            PipeTile, TileProperties, get_tile_properties_from_path,
         },
     },
     parallax::{
@@ -38,6 +40,7 @@ impl Plugin for MapPlugin {
     fn build(&self, app: &mut App) {
         app.init_state::<LevelState>()
             .insert_resource(CurrentLevelInfo {
+                level: Level::Level1,
                 data: Level::Level1.get_path(),
             })
             .add_systems(OnEnter(LevelState::Loading), load_map_assets)
@@ -57,13 +60,18 @@ impl Plugin for MapPlugin {
                     falling_tiles_system,
                     bouncy_platforms_system,
                     damage_platforms_system,
-                    one_way_platform_collision_system,
+                    drop_through_platform_system,
+                    drop_through_timer_system,
+                    one_way_platform_collision_system.after(drop_through_platform_system),
+                    end_level_system,
                 )
                     .run_if(in_state(GameState::Game))
                     .run_if(in_state(LevelState::LevelLoaded)),
             )
-            .add_systems(OnExit(GameState::Game), cleanup_level_tiles)
-            .add_systems(OnExit(GameState::Game), despawn_parallax_layers);
+            .add_systems(
+                OnExit(GameState::Game),
+                (cleanup_level_tiles, despawn_parallax_layers).run_if(not(is_pause_transition)),
+            );
     }
 }
 fn despawn_parallax_layers(mut commands: Commands, query: Query<Entity, With<ParallaxLayer>>) {
@@ -129,6 +137,9 @@ pub fn spawn_level_tiles(
 }
 
 pub const ONE_WAY_PLATFORM_GROUP: Group = Group::GROUP_2;
+// Margen entre los pies y la parte de arriba de una plataforma para contar como encima
+const PLATFORM_FEET_TOLERANCE: f32 = 4.0;
+const DROP_THROUGH_TIME: f32 = 0.3;
 
 // Función para spawnear tiles especiales
 fn spawn_special_tile(
@@ -248,25 +259,109 @@ fn create_collider_from_shape(shape: &ColliderShape, tile_size: f32) -> Collider
     }
 }
 
+// Caja del collider en el mundo: esquina inferior izquierda y superior derecha
+pub fn collider_bounds(transform: &GlobalTransform, collider: &Collider) -> (Vec2, Vec2) {
+    let aabb = collider.raw.compute_local_aabb();
+    let (scale, _, translation) = transform.to_scale_rotation_translation();
+    let center = translation.xy();
+    (
+        center + Vec2::new(aabb.mins.x, aabb.mins.y) * scale.xy(),
+        center + Vec2::new(aabb.maxs.x, aabb.maxs.y) * scale.xy(),
+    )
+}
+
+// Pulsar abajo de pie sobre una plataforma de un sentido la atraviesa durante un momento
+pub fn drop_through_platform_system(
+    mut commands: Commands,
+    player_actions: Res<PlayerActions>,
+    player_query: Query<
+        (
+            Entity,
+            &PlayerId,
+            &GlobalTransform,
+            &Collider,
+            &KinematicCharacterControllerOutput,
+        ),
+        Without<DropThrough>,
+    >,
+    platform_query: Query<(&GlobalTransform, &Collider), With<FallingTile>>,
+) {
+    for (entity, player, transform, collider, output) in player_query.iter() {
+        if !output.grounded || !player_actions.get(player).just_pressed(Action::MoveDown) {
+            continue;
+        }
+        let (player_min, player_max) = collider_bounds(transform, collider);
+        let on_platform = platform_query
+            .iter()
+            .any(|(platform_transform, platform_collider)| {
+                let (platform_min, platform_max) =
+                    collider_bounds(platform_transform, platform_collider);
+                player_max.x > platform_min.x
+                    && player_min.x < platform_max.x
+                    && (player_min.y - platform_max.y).abs() <= PLATFORM_FEET_TOLERANCE
+            });
+        if on_platform {
+            commands.entity(entity).insert(DropThrough {
+                timer: Timer::from_seconds(DROP_THROUGH_TIME, TimerMode::Once),
+            });
+        }
+    }
+}
+
+pub fn drop_through_timer_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut DropThrough)>,
+) {
+    for (entity, mut drop_through) in &mut query {
+        drop_through.timer.tick(time.delta());
+        if drop_through.timer.finished() {
+            commands.entity(entity).remove::<DropThrough>();
+        }
+    }
+}
+
+// El player solo choca con las plataformas de un sentido al caer con los pies por encima
+// de ellas; si está dentro de alguna la atraviesa en vez de subirse de golpe
 pub fn one_way_platform_collision_system(
     mut player_query: Query<
-        (&PlayerVelocity, &mut KinematicCharacterController),
+        (
+            Entity,
+            &PlayerVelocity,
+            &GlobalTransform,
+            &Collider,
+            &mut KinematicCharacterController,
+        ),
         With<PlayerCharacter>,
     >,
+    drop_through_query: Query<(), With<DropThrough>>,
+    platform_query: Query<(&GlobalTransform, &Collider), With<FallingTile>>,
 ) {
-    if let Ok((player_velocity, mut character_controller)) = player_query.single_mut() {
-        // Si el jugador se está moviendo hacia abajo, habilitamos la colisión con la plataforma.
-        if player_velocity.velocity.y < 0.0 {
-            println!("____Falling");
-            // Reemplazamos el filtro con uno nuevo que incluye el grupo de la plataforma.
-            character_controller.filter_groups = Some(CollisionGroups { ..default() });
-        } else {
-            println!("____Jumping");
-            // Si no se está moviendo hacia abajo, el filtro se restablece para ignorar la plataforma.
-            character_controller.filter_groups = Some(CollisionGroups {
-                memberships: PLAYER_GROUP,
-                filters: Group::ALL & !ONE_WAY_PLATFORM_GROUP,
-            });
-        }
+    for (entity, player_velocity, transform, collider, mut character_controller) in
+        player_query.iter_mut()
+    {
+        let dropping = drop_through_query.contains(entity);
+        let (player_min, player_max) = collider_bounds(transform, collider);
+        let inside_platform =
+            platform_query
+                .iter()
+                .any(|(platform_transform, platform_collider)| {
+                    let (platform_min, platform_max) =
+                        collider_bounds(platform_transform, platform_collider);
+                    player_max.x > platform_min.x
+                        && player_min.x < platform_max.x
+                        && player_min.y < platform_max.y - PLATFORM_FEET_TOLERANCE
+                        && player_max.y > platform_min.y
+                });
+
+        character_controller.filter_groups =
+            if player_velocity.velocity.y <= 0.0 && !inside_platform && !dropping {
+                Some(CollisionGroups { ..default() })
+            } else {
+                Some(CollisionGroups {
+                    memberships: PLAYER_GROUP,
+                    filters: Group::ALL & !ONE_WAY_PLATFORM_GROUP,
+                })
+            };
     }
 }
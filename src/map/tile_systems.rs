@@ -1,9 +1,10 @@
 use crate::{
+    game_state::GameState,
     map::components::{
         BouncyPlatform, DamageTile, FallingState, FallingTile, TileProperties, TileType,
     },
     physics::Velocity as PlayerVelocity,
-    player::components::{Health, Invincibility, PlayerCharacter},
+    player::components::{Health, Invincibility, PlayerCharacter, PlayerHit},
 };
 use bevy::prelude::*;
 use bevy_rapier2d::prelude::{KinematicCharacterControllerOutput, RigidBody};
@@ -59,7 +60,7 @@ pub fn trigger_falling_tiles_system(
     mut falling_tiles: Query<(Entity, &mut FallingTile, &TileProperties)>,
     player_query: Query<&KinematicCharacterControllerOutput, With<PlayerCharacter>>,
 ) {
-    if let Ok(controller_output) = player_query.single() {
+    for controller_output in player_query.iter() {
         for collision in &controller_output.collisions {
             let collided_entity = collision.entity;
 
@@ -76,25 +77,44 @@ pub fn trigger_falling_tiles_system(
     }
 }
 
+// Sistema que termina el nivel cuando el player toca el tile EndLevel
+pub fn end_level_system(
+    tile_query: Query<&TileProperties>,
+    player_query: Query<&KinematicCharacterControllerOutput, With<PlayerCharacter>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    // Basta con que llegue uno de los players
+    for controller_output in player_query.iter() {
+        for collision in &controller_output.collisions {
+            if let Ok(tile_properties) = tile_query.get(collision.entity)
+                && tile_properties.tile_type == TileType::EndLevel
+            {
+                next_state.set(GameState::LevelComplete);
+                return;
+            }
+        }
+    }
+}
+
 pub fn bouncy_platforms_system(
     mut bouncy_query: Query<(Entity, &mut BouncyPlatform)>,
-    player_query: Query<&KinematicCharacterControllerOutput, With<PlayerCharacter>>,
-    player_velocity_query: Query<&PlayerVelocity, With<PlayerCharacter>>,
+    player_query: Query<
+        (&KinematicCharacterControllerOutput, &PlayerVelocity),
+        With<PlayerCharacter>,
+    >,
 ) {
-    if let Ok(controller_output) = player_query.single() {
-        if let Ok(player_velocity) = player_velocity_query.single() {
-            for collision in &controller_output.collisions {
-                let collided_entity = collision.entity;
+    for (controller_output, player_velocity) in player_query.iter() {
+        for collision in &controller_output.collisions {
+            let collided_entity = collision.entity;
 
-                if let Ok((_, mut bouncy_platform)) = bouncy_query.get_mut(collided_entity) {
-                    // Calcula la dirección del rebote
-                    let direction = (bouncy_platform.velocity - player_velocity.velocity)
-                        .normalize_or_zero()
-                        * bouncy_platform.bounce_force;
+            if let Ok((_, mut bouncy_platform)) = bouncy_query.get_mut(collided_entity) {
+                // Calcula la dirección del rebote
+                let direction = (bouncy_platform.velocity - player_velocity.velocity)
+                    .normalize_or_zero()
+                    * bouncy_platform.bounce_force;
 
-                    // Aplica la nueva velocidad a la plataforma rebotadora
-                    bouncy_platform.velocity = direction;
-                }
+                // Aplica la nueva velocidad a la plataforma rebotadora
+                bouncy_platform.velocity = direction;
             }
         }
     }
@@ -111,42 +131,41 @@ pub fn damage_platforms_system(
         With<PlayerCharacter>,
     >,
     mut health_query: Query<&mut Health, With<PlayerCharacter>>,
-    mut velocity_query: Query<&mut PlayerVelocity, With<PlayerCharacter>>,
     damage_tile_query: Query<&DamageTile>,
+    mut hit_events: EventWriter<PlayerHit>,
 ) {
-    match player_query.single() {
-        Ok((_, _, Some(_))) => {
-            println!("can't touch me!");
-            return;
+    for (player_entity, controller_output, invincibility) in player_query.iter() {
+        if invincibility.is_some() {
+            continue;
         }
-        Ok((player_entity, controller_output, _)) => {
-            for collision in &controller_output.collisions {
-                if let Ok(damage_tile) = damage_tile_query.get(collision.entity) {
-                    match (
-                        health_query.get_mut(player_entity),
-                        velocity_query.get_mut(player_entity),
-                    ) {
-                        (Ok(mut player_health), Ok(mut player_velocity)) => {
-                            if player_health.current > 0 {
-                                player_health.current -= damage_tile.damage_amount as u32;
-                                commands
-                                    .entity(player_entity)
-                                    .insert(Invincibility::new(1.9));
-                            }
 
-                            let n = collision.hit.details.unwrap().normal2; // Vec2 válido si usas la versión actual
-                            let v = player_velocity.velocity;
-                            let reflected = v - 2.0 * v.dot(n) * n;
-                            player_velocity.velocity = reflected * 0.9; // 90% de energía del rebote
-                        }
-                        _ => {}
-                    }
-                }
+        for collision in &controller_output.collisions {
+            let Ok(damage_tile) = damage_tile_query.get(collision.entity) else {
+                continue;
+            };
+            let Ok(mut player_health) = health_query.get_mut(player_entity) else {
+                continue;
+            };
+            if player_health.current == 0 {
+                continue;
             }
-        }
-        Err(_) => {
-            println!("Player entity not found");
-            return;
+
+            player_health.current = player_health
+                .current
+                .saturating_sub(damage_tile.damage_amount as u32);
+            commands
+                .entity(player_entity)
+                .insert(Invincibility::new(1.9));
+            // La normal del contacto apunta desde el tile hacia el player
+            hit_events.write(PlayerHit {
+                player: player_entity,
+                direction: collision
+                    .hit
+                    .details
+                    .map_or(Vec2::Y, |details| details.normal2),
+            });
+            // Un solo golpe por frame aunque toque varios tiles de daño
+            break;
         }
     }
 }
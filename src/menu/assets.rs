@@ -5,8 +5,10 @@ use crate::{game_state::GameState, menu::components::MenuLoadingState};
 #[derive(Resource, Debug, PartialEq, Eq, Clone, Copy, Hash)]
 pub enum Menu {
     StartMenu,
+    CharacterSelectMenu,
     PauseMenu,
     GameOverMenu,
+    LevelCompleteMenu,
 }
 pub struct MenuPaths {
     pub background: String,
@@ -21,7 +23,10 @@ impl MenuPaths {
 impl Menu {
     pub fn get_paths(&self) -> MenuPaths {
         match self {
-            Menu::StartMenu | Menu::PauseMenu => MenuPaths::new("menu/menu_background.png"),
+            Menu::StartMenu
+            | Menu::CharacterSelectMenu
+            | Menu::PauseMenu
+            | Menu::LevelCompleteMenu => MenuPaths::new("menu/menu_background.png"),
             Menu::GameOverMenu => MenuPaths::new("menu/game_over_background.png"),
         }
     }
@@ -44,8 +49,10 @@ pub fn load_menu_assets(
 
     let menu_info = match game_state.get() {
         GameState::MainMenu => Menu::StartMenu.get_paths(),
+        GameState::CharacterSelect => Menu::CharacterSelectMenu.get_paths(),
         GameState::PauseMenu => Menu::PauseMenu.get_paths(),
         GameState::GameOver => Menu::GameOverMenu.get_paths(),
+        GameState::LevelComplete => Menu::LevelCompleteMenu.get_paths(),
         _ => panic!("Invalid game state"),
     };
 
@@ -13,6 +13,12 @@ pub struct OnMainMenuScreen;
 #[derive(Component)]
 pub enum MenuButtonAction {
     Play,
+    // Partida para dos jugadores en el mismo equipo
+    PlayCoop,
+    // Elige para el siguiente jugador el gato de ese índice en PlayableCharacters;
+    // cuando han elegido todos empieza la partida
+    SelectCharacter(usize),
+    Resume,
     Controls,
     Options,
     Levels,
@@ -26,3 +32,7 @@ pub struct OriginalColor(pub BackgroundColor);
 
 #[derive(Component)]
 pub struct MenuWidget;
+
+// Título de la selección de personaje, que indica a qué jugador le toca elegir
+#[derive(Component)]
+pub struct CharacterSelectTitle;
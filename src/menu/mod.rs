@@ -3,12 +3,25 @@ mod components;
 
 use crate::{
     game_state::{GameState, LevelState},
+    input::components::{Action, ActionState},
+    map::components::CurrentLevelInfo,
     menu::{
         assets::{MenuAssets, load_menu_assets},
-        components::{MenuButtonAction, MenuLoadingState, MenuWidget, OriginalColor},
+        components::{
+            CharacterSelectTitle, MenuButtonAction, MenuLoadingState, MenuWidget, OriginalColor,
+        },
+    },
+    player::components::{
+        HALF_HEARTS_PER_HEART, Lives, PlayableCharacters, PlayerCount, SelectedCharacters,
     },
+    save::{
+        components::{RunStats, SaveData},
+        format_time,
+    },
+    speedrun::components::LevelTimer,
 };
-use bevy::prelude::*;
+use bevy::{ecs::system::SystemParam, prelude::*};
+use bevy_rapier2d::plugin::RapierConfiguration;
 
 const HOVERED_BUTTON_SCALE: f32 = 1.1; // La escala que aplicaremos al botón en hover
 const PRESSED_BUTTON_COLOR: Color = Color::srgb(0.5, 0.5, 0.5);
@@ -22,6 +35,7 @@ const CONTROLS_BUTTON_COLOR: Color = Color::srgb(1.0, 1.0, 0.0); // Amarillo bri
 const OPTIONS_BUTTON_COLOR: Color = Color::srgb(1.0, 0.0, 0.6); // Rosa brillante
 const LEVELS_BUTTON_COLOR: Color = Color::srgb(0.4, 1.0, 0.0); // Verde brillante
 const QUIT_BUTTON_COLOR: Color = Color::srgb(0.6, 0.2, 0.6); // Morado brillante
+const CHARACTER_CARD_COLOR: Color = Color::srgb(1.0, 0.8, 0.4); // Naranja claro
 
 // Un componente marcador para identificar las entidades del menú
 
@@ -32,22 +46,30 @@ impl Plugin for MenuPlugin {
         app.init_state::<MenuLoadingState>()
             .init_resource::<MenuAssets>()
             .add_systems(OnEnter(GameState::MainMenu), load_menu_assets)
+            .add_systems(OnEnter(GameState::CharacterSelect), load_menu_assets)
             .add_systems(OnEnter(GameState::PauseMenu), load_menu_assets)
             .add_systems(OnEnter(GameState::GameOver), load_menu_assets)
+            .add_systems(OnEnter(GameState::LevelComplete), load_menu_assets)
             .add_systems(
                 Update,
                 check_menu_assets_loaded
                     .run_if(in_state(MenuLoadingState::Loading))
                     .run_if(
                         in_state(GameState::MainMenu)
+                            .or(in_state(GameState::CharacterSelect))
                             .or(in_state(GameState::PauseMenu))
-                            .or(in_state(GameState::GameOver)),
+                            .or(in_state(GameState::GameOver))
+                            .or(in_state(GameState::LevelComplete)),
                     ),
             )
             .add_systems(
                 OnEnter(MenuLoadingState::Ready),
                 spawn_main_menu_setup.run_if(in_state(GameState::MainMenu)),
             )
+            .add_systems(
+                OnEnter(MenuLoadingState::Ready),
+                spawn_character_select_setup.run_if(in_state(GameState::CharacterSelect)),
+            )
             .add_systems(
                 OnEnter(MenuLoadingState::Ready),
                 spawn_pause_menu_setup.run_if(in_state(GameState::PauseMenu)),
@@ -56,18 +78,39 @@ impl Plugin for MenuPlugin {
                 OnEnter(MenuLoadingState::Ready),
                 spawn_gameover_menu_setup.run_if(in_state(GameState::GameOver)),
             )
+            .add_systems(
+                OnEnter(MenuLoadingState::Ready),
+                spawn_level_complete_menu_setup.run_if(in_state(GameState::LevelComplete)),
+            )
             .add_systems(
                 Update,
-                menu_button_system
+                (menu_button_system, menu_navigation)
                     .run_if(in_state(MenuLoadingState::Ready))
                     .run_if(
                         in_state(GameState::MainMenu)
-                            .or(in_state(GameState::PauseMenu).or(in_state(GameState::GameOver))),
+                            .or(in_state(GameState::CharacterSelect))
+                            .or(in_state(GameState::PauseMenu).or(in_state(GameState::GameOver)))
+                            .or(in_state(GameState::LevelComplete)),
                     ),
             )
+            .add_systems(
+                Update,
+                update_character_select_title
+                    .run_if(in_state(MenuLoadingState::Ready))
+                    .run_if(in_state(GameState::CharacterSelect)),
+            )
+            .add_systems(
+                Update,
+                toggle_pause_system
+                    .run_if(in_state(GameState::Game).or(in_state(GameState::PauseMenu))),
+            )
+            .add_systems(OnEnter(GameState::PauseMenu), pause_physics)
+            .add_systems(OnExit(GameState::PauseMenu), resume_physics)
             .add_systems(OnExit(GameState::MainMenu), despawn_menu)
+            .add_systems(OnExit(GameState::CharacterSelect), despawn_menu)
             .add_systems(OnExit(GameState::PauseMenu), despawn_menu)
-            .add_systems(OnExit(GameState::GameOver), despawn_menu);
+            .add_systems(OnExit(GameState::GameOver), despawn_menu)
+            .add_systems(OnExit(GameState::LevelComplete), despawn_menu);
     }
 }
 
@@ -82,9 +125,7 @@ fn menu_button_system(
         ),
         (Changed<Interaction>, With<Button>),
     >,
-    mut app_exit_events: EventWriter<AppExit>,
-    mut next_game_state: ResMut<NextState<GameState>>,
-    mut next_level_state: ResMut<NextState<LevelState>>,
+    mut menu_actions: MenuActions,
 ) {
     for (interaction, menu_button_action, mut background_color, original_color, mut transform) in
         &mut interaction_query
@@ -96,23 +137,7 @@ fn menu_button_system(
                 transform.scale = Vec3::ONE;
 
                 // Llama a la acción correspondiente del botón
-                match menu_button_action {
-                    MenuButtonAction::Play => {
-                        next_game_state.set(GameState::Game);
-                        next_level_state.set(LevelState::Loading);
-                    }
-                    MenuButtonAction::PlayAgain => {
-                        next_game_state.set(GameState::Game);
-                        next_level_state.set(LevelState::Loading);
-                    }
-                    MenuButtonAction::GoToMainMenu => next_game_state.set(GameState::MainMenu),
-                    MenuButtonAction::Controls => info!("Controls button pressed!"),
-                    MenuButtonAction::Options => info!("Options button pressed!"),
-                    MenuButtonAction::Levels => info!("Levels button pressed!"),
-                    MenuButtonAction::Quit => {
-                        app_exit_events.write(AppExit::Success);
-                    }
-                }
+                menu_actions.run(menu_button_action);
                 print!("Pressed button!");
             }
             Interaction::Hovered => {
@@ -131,6 +156,163 @@ fn menu_button_system(
     }
 }
 
+// Recursos que modifican las acciones de los botones del menú
+#[derive(SystemParam)]
+struct MenuActions<'w> {
+    app_exit_events: EventWriter<'w, AppExit>,
+    next_game_state: ResMut<'w, NextState<GameState>>,
+    next_level_state: ResMut<'w, NextState<LevelState>>,
+    lives: ResMut<'w, Lives>,
+    selected_characters: ResMut<'w, SelectedCharacters>,
+    player_count: ResMut<'w, PlayerCount>,
+}
+
+impl MenuActions<'_> {
+    fn run(&mut self, action: &MenuButtonAction) {
+        match action {
+            MenuButtonAction::Play => self.start_character_select(1),
+            MenuButtonAction::PlayCoop => self.start_character_select(2),
+            MenuButtonAction::SelectCharacter(index) => {
+                self.selected_characters.0.push(*index);
+                if self.selected_characters.0.len() < self.player_count.0 {
+                    return;
+                }
+                *self.lives = Lives::default();
+                self.next_game_state.set(GameState::Game);
+                self.next_level_state.set(LevelState::Loading);
+            }
+            MenuButtonAction::Resume => self.next_game_state.set(GameState::Game),
+            MenuButtonAction::PlayAgain => {
+                *self.lives = Lives::default();
+                self.next_game_state.set(GameState::Game);
+                self.next_level_state.set(LevelState::Loading);
+            }
+            MenuButtonAction::GoToMainMenu => self.next_game_state.set(GameState::MainMenu),
+            MenuButtonAction::Controls => info!("Controls button pressed!"),
+            MenuButtonAction::Options => info!("Options button pressed!"),
+            MenuButtonAction::Levels => info!("Levels button pressed!"),
+            MenuButtonAction::Quit => {
+                self.app_exit_events.write(AppExit::Success);
+            }
+        }
+    }
+
+    fn start_character_select(&mut self, players: usize) {
+        *self.player_count = PlayerCount(players);
+        self.selected_characters.0.clear();
+        self.next_game_state.set(GameState::CharacterSelect);
+    }
+}
+
+fn character_select_title(player_count: &PlayerCount, selected: &SelectedCharacters) -> String {
+    if player_count.is_coop() {
+        format!("Player {}: choose your cat", selected.0.len() + 1)
+    } else {
+        "Choose your cat".to_string()
+    }
+}
+
+// En co-op cada jugador elige por turnos; el título dice a quién le toca
+fn update_character_select_title(
+    player_count: Res<PlayerCount>,
+    selected: Res<SelectedCharacters>,
+    mut title_query: Query<&mut Text, With<CharacterSelectTitle>>,
+) {
+    if !selected.is_changed() {
+        return;
+    }
+    for mut text in title_query.iter_mut() {
+        text.0 = character_select_title(&player_count, &selected);
+    }
+}
+
+// Navegación del menú sin ratón con las acciones MenuUp, MenuDown y MenuConfirm, que
+// por defecto son las flechas, WASD, la cruceta y el stick para cambiar de botón y
+// Enter, Espacio o South para pulsarlo
+fn menu_navigation(
+    actions: Res<ActionState>,
+    mut focus: Local<Option<usize>>,
+    new_buttons: Query<(), Added<Button>>,
+    mut button_query: Query<(&MenuButtonAction, &GlobalTransform, &mut Transform), With<Button>>,
+    mut menu_actions: MenuActions,
+) {
+    // Un menú nuevo empieza sin botón seleccionado
+    if !new_buttons.is_empty() {
+        *focus = None;
+    }
+
+    let step = if actions.just_pressed(Action::MenuUp) {
+        -1
+    } else if actions.just_pressed(Action::MenuDown) {
+        1
+    } else {
+        0
+    };
+    let confirm = actions.just_pressed(Action::MenuConfirm);
+
+    if step == 0 && !confirm {
+        return;
+    }
+
+    // Botones ordenados de arriba a abajo y, en la misma fila, de izquierda a derecha
+    let mut buttons: Vec<_> = button_query.iter_mut().collect();
+    if buttons.is_empty() {
+        return;
+    }
+    buttons.sort_by(|(_, a, _), (_, b, _)| {
+        let (a, b) = (a.translation(), b.translation());
+        a.y.total_cmp(&b.y).then(a.x.total_cmp(&b.x))
+    });
+
+    let focused = match *focus {
+        Some(index) if index < buttons.len() => {
+            (index as isize + step).rem_euclid(buttons.len() as isize) as usize
+        }
+        _ => 0,
+    };
+    *focus = Some(focused);
+
+    for (index, (_, _, transform)) in buttons.iter_mut().enumerate() {
+        transform.scale = if index == focused {
+            Vec3::splat(HOVERED_BUTTON_SCALE)
+        } else {
+            Vec3::ONE
+        };
+    }
+
+    if confirm {
+        let action = buttons[focused].0;
+        menu_actions.run(action);
+    }
+}
+
+// La acción Pause (Escape por defecto) pausa la partida o la reanuda desde el menú de pausa
+fn toggle_pause_system(
+    actions: Res<ActionState>,
+    game_state: Res<State<GameState>>,
+    mut next_game_state: ResMut<NextState<GameState>>,
+) {
+    if actions.just_pressed(Action::Pause) {
+        match game_state.get() {
+            GameState::Game => next_game_state.set(GameState::PauseMenu),
+            GameState::PauseMenu => next_game_state.set(GameState::Game),
+            _ => {}
+        }
+    }
+}
+
+fn pause_physics(mut rapier_config: Query<&mut RapierConfiguration>) {
+    for mut config in rapier_config.iter_mut() {
+        config.physics_pipeline_active = false;
+    }
+}
+
+fn resume_physics(mut rapier_config: Query<&mut RapierConfiguration>) {
+    for mut config in rapier_config.iter_mut() {
+        config.physics_pipeline_active = true;
+    }
+}
+
 fn check_menu_assets_loaded(
     asset_server: Res<AssetServer>,
     menu_assets: Res<MenuAssets>,
@@ -208,6 +390,19 @@ fn spawn_main_menu_setup(mut commands: Commands, menu_assets: Res<MenuAssets>) {
                         TextColor(TEXT_COLOR),
                     ),]
                 ),
+                (
+                    Button,
+                    button_node.clone(),
+                    BackgroundColor(PLAY_BUTTON_COLOR),
+                    OriginalColor(BackgroundColor(PLAY_BUTTON_COLOR)),
+                    BorderColor::from(Color::BLACK),
+                    MenuButtonAction::PlayCoop,
+                    children![(
+                        Text::new("Co-op"),
+                        button_text_font.clone(),
+                        TextColor(TEXT_COLOR),
+                    ),]
+                ),
                 (
                     Button,
                     button_node.clone(),
@@ -260,6 +455,156 @@ fn spawn_main_menu_setup(mut commands: Commands, menu_assets: Res<MenuAssets>) {
         )],
     ));
 }
+// Pantalla de selección: una tarjeta por gato con su retrato, estadísticas y proyectil
+fn spawn_character_select_setup(
+    mut commands: Commands,
+    menu_assets: Res<MenuAssets>,
+    characters: Res<PlayableCharacters>,
+    player_count: Res<PlayerCount>,
+    selected: Res<SelectedCharacters>,
+) {
+    let card_node = Node {
+        width: Val::Px(150.0),
+        margin: UiRect::all(Val::Px(10.0)),
+        padding: UiRect::all(Val::Px(8.0)),
+        flex_direction: FlexDirection::Column,
+        align_items: AlignItems::Center,
+        border: UiRect::all(Val::Px(4.0)),
+        ..default()
+    };
+    let name_font = TextFont {
+        font_size: 26.0,
+        font: menu_assets.text_font.clone(),
+        ..default()
+    };
+    let stats_font = TextFont {
+        font_size: 16.0,
+        font: menu_assets.text_font.clone(),
+        ..default()
+    };
+    let button_text_font = TextFont {
+        font_size: 33.0,
+        font: menu_assets.text_font.clone(),
+        ..default()
+    };
+
+    commands
+        .spawn((
+            MenuWidget,
+            ImageNode {
+                image: menu_assets.background.clone(),
+                ..default()
+            },
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                CharacterSelectTitle,
+                Text::new(character_select_title(&player_count, &selected)),
+                TextFont {
+                    font_size: 67.0,
+                    font: menu_assets.title_font.clone(),
+                    ..default()
+                },
+                TextColor(TITLE_COLOR),
+                Node {
+                    margin: UiRect::all(Val::Px(30.0)),
+                    ..default()
+                },
+            ));
+
+            parent
+                .spawn(Node {
+                    flex_direction: FlexDirection::Row,
+                    flex_wrap: FlexWrap::Wrap,
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                })
+                .with_children(|row| {
+                    for (index, character) in characters.0.iter().enumerate() {
+                        let stats = character.character_stats;
+                        row.spawn((
+                            Button,
+                            card_node.clone(),
+                            BackgroundColor(CHARACTER_CARD_COLOR),
+                            OriginalColor(BackgroundColor(CHARACTER_CARD_COLOR)),
+                            BorderColor::from(Color::BLACK),
+                            MenuButtonAction::SelectCharacter(index),
+                            children![
+                                (
+                                    ImageNode {
+                                        image: character.sheet.image.clone(),
+                                        texture_atlas: Some(TextureAtlas {
+                                            layout: character.sheet.layout.clone(),
+                                            index: 0,
+                                        }),
+                                        ..default()
+                                    },
+                                    Node {
+                                        width: Val::Px(96.0),
+                                        height: Val::Px(96.0),
+                                        ..default()
+                                    },
+                                ),
+                                (
+                                    Text::new(character.name.clone()),
+                                    name_font.clone(),
+                                    TextColor(TEXT_COLOR),
+                                ),
+                                (
+                                    Text::new(format!(
+                                        "Speed {:.0}\nJump {:.0}\nHearts {}",
+                                        stats.run_speed,
+                                        stats.jump_velocity,
+                                        stats.health.div_ceil(HALF_HEARTS_PER_HEART)
+                                    )),
+                                    stats_font.clone(),
+                                    TextColor(TEXT_COLOR),
+                                ),
+                                (
+                                    ImageNode {
+                                        image: character.projectile.clone(),
+                                        ..default()
+                                    },
+                                    Node {
+                                        width: Val::Px(28.0),
+                                        height: Val::Px(28.0),
+                                        margin: UiRect::top(Val::Px(6.0)),
+                                        ..default()
+                                    },
+                                ),
+                            ],
+                        ));
+                    }
+                });
+
+            parent.spawn((
+                Button,
+                Node {
+                    width: Val::Px(200.0),
+                    height: Val::Px(65.0),
+                    margin: UiRect::all(Val::Px(20.0)),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    border: UiRect::all(Val::Px(4.0)),
+                    ..default()
+                },
+                BackgroundColor(QUIT_BUTTON_COLOR),
+                OriginalColor(BackgroundColor(QUIT_BUTTON_COLOR)),
+                BorderColor::from(Color::BLACK),
+                MenuButtonAction::GoToMainMenu,
+                children![(Text::new("Back"), button_text_font, TextColor(TEXT_COLOR))],
+            ));
+        });
+}
+
 fn spawn_pause_menu_setup(mut commands: Commands, menu_assets: Res<MenuAssets>) {
     let button_node = Node {
         width: Val::Px(200.0),
@@ -315,9 +660,9 @@ fn spawn_pause_menu_setup(mut commands: Commands, menu_assets: Res<MenuAssets>)
                     BackgroundColor(PLAY_BUTTON_COLOR),
                     OriginalColor(BackgroundColor(PLAY_BUTTON_COLOR)),
                     BorderColor::from(Color::BLACK),
-                    MenuButtonAction::Play,
+                    MenuButtonAction::Resume,
                     children![(
-                        Text::new("Play"),
+                        Text::new("Resume"),
                         button_text_font.clone(),
                         TextColor(TEXT_COLOR),
                     ),]
@@ -374,7 +719,12 @@ fn spawn_pause_menu_setup(mut commands: Commands, menu_assets: Res<MenuAssets>)
         )],
     ));
 }
-fn spawn_gameover_menu_setup(mut commands: Commands, menu_assets: Res<MenuAssets>) {
+fn spawn_gameover_menu_setup(
+    mut commands: Commands,
+    menu_assets: Res<MenuAssets>,
+    level_info: Res<CurrentLevelInfo>,
+    save_data: Res<SaveData>,
+) {
     let button_node = Node {
         width: Val::Px(220.0),
         height: Val::Px(65.0),
@@ -390,6 +740,11 @@ fn spawn_gameover_menu_setup(mut commands: Commands, menu_assets: Res<MenuAssets
         font: menu_assets.text_font.clone(),
         ..default()
     };
+    let records_text_font = TextFont {
+        font_size: 24.0,
+        font: menu_assets.text_font.clone(),
+        ..default()
+    };
     commands.spawn((
         MenuWidget,
         ImageNode {
@@ -423,6 +778,125 @@ fn spawn_gameover_menu_setup(mut commands: Commands, menu_assets: Res<MenuAssets
                         ..default()
                     },
                 ),
+                (
+                    Text::new(best_record_text(&level_info, &save_data)),
+                    records_text_font.clone(),
+                    TextColor(TITLE_COLOR),
+                ),
+                (
+                    Button,
+                    button_node.clone(),
+                    BackgroundColor(PLAY_BUTTON_COLOR),
+                    OriginalColor(BackgroundColor(PLAY_BUTTON_COLOR)),
+                    BorderColor::from(Color::BLACK),
+                    MenuButtonAction::PlayAgain,
+                    children![(
+                        Text::new("Play Again"),
+                        button_text_font.clone(),
+                        TextColor(TEXT_COLOR),
+                    ),]
+                ),
+                (
+                    Button,
+                    button_node,
+                    BackgroundColor(QUIT_BUTTON_COLOR),
+                    OriginalColor(BackgroundColor(QUIT_BUTTON_COLOR)),
+                    BorderColor::from(Color::BLACK),
+                    MenuButtonAction::GoToMainMenu,
+                    children![(Text::new("Back"), button_text_font, TextColor(TEXT_COLOR),),]
+                ),
+            ]
+        )],
+    ));
+}
+fn spawn_level_complete_menu_setup(
+    mut commands: Commands,
+    menu_assets: Res<MenuAssets>,
+    level_info: Res<CurrentLevelInfo>,
+    save_data: Res<SaveData>,
+    run_stats: Res<RunStats>,
+    level_timer: Res<LevelTimer>,
+) {
+    let button_node = Node {
+        width: Val::Px(220.0),
+        height: Val::Px(65.0),
+        margin: UiRect::all(Val::Px(20.0)),
+        justify_content: JustifyContent::Center,
+        align_items: AlignItems::Center,
+        border: UiRect::all(Val::Px(4.0)),
+        ..default()
+    };
+
+    let button_text_font = TextFont {
+        font_size: 33.0,
+        font: menu_assets.text_font.clone(),
+        ..default()
+    };
+    let records_text_font = TextFont {
+        font_size: 24.0,
+        font: menu_assets.text_font.clone(),
+        ..default()
+    };
+
+    let score_text = if run_stats.new_best_score {
+        format!("Score: {}  New record!", run_stats.score)
+    } else {
+        format!("Score: {}", run_stats.score)
+    };
+    let time_text = if run_stats.new_best_time {
+        format!("Time: {}  New record!", format_time(level_timer.elapsed))
+    } else {
+        format!("Time: {}", format_time(level_timer.elapsed))
+    };
+
+    commands.spawn((
+        MenuWidget,
+        ImageNode {
+            image: menu_assets.background.clone(),
+            ..default()
+        },
+        Node {
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        children![(
+            Node {
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            children![
+                (
+                    Text::new("Level Complete"),
+                    TextFont {
+                        font_size: 87.0,
+                        font: menu_assets.title_font.clone(),
+                        ..default()
+                    },
+                    TextColor(TITLE_COLOR),
+                    Node {
+                        margin: UiRect::all(Val::Px(50.0)),
+                        ..default()
+                    },
+                ),
+                (
+                    Text::new(score_text),
+                    records_text_font.clone(),
+                    TextColor(TITLE_COLOR),
+                ),
+                (
+                    Text::new(time_text),
+                    records_text_font.clone(),
+                    TextColor(TITLE_COLOR),
+                ),
+                (
+                    Text::new(best_record_text(&level_info, &save_data)),
+                    records_text_font,
+                    TextColor(TITLE_COLOR),
+                ),
                 (
                     Button,
                     button_node.clone(),
@@ -449,6 +923,22 @@ fn spawn_gameover_menu_setup(mut commands: Commands, menu_assets: Res<MenuAssets
         )],
     ));
 }
+
+// Texto con las mejores marcas guardadas del nivel actual
+fn best_record_text(level_info: &CurrentLevelInfo, save_data: &SaveData) -> String {
+    match save_data.record(level_info.level) {
+        Some(record) if record.completed => format!(
+            "Best score: {}  Best time: {}",
+            record.best_score,
+            record
+                .best_time
+                .map(format_time)
+                .unwrap_or_else(|| "--".to_string())
+        ),
+        _ => "Best score: --  Best time: --".to_string(),
+    }
+}
+
 // Sistema que elimina todas las entidades del menú al salir del estado MainMenu
 fn despawn_menu(mut commands: Commands, menu_query: Query<Entity, With<MenuWidget>>) {
     for entity in menu_query.iter() {
@@ -10,6 +10,11 @@ use crate::{
     player::components::PlayerCharacter,
 };
 
+// Espacio que se deja entre cada player y el borde de la pantalla al encuadrar a varios
+const CAMERA_PLAYER_MARGIN: f32 = 200.0;
+// Cuánto se puede alejar la cámara como máximo para encuadrar a todos los players
+const MAX_CAMERA_ZOOM: f32 = 1.6;
+
 // Sistema de parallax con repetición infinita usando wrapping matemático
 pub fn infinite_parallax_system(
     time: Res<Time>,
@@ -102,56 +107,73 @@ pub fn configure_parallax_textures(
     next_state.set(LevelState::LevelLoaded);
 }
 
+// La cámara sigue al punto medio de los players y, en co-op, se aleja lo necesario para
+// encuadrarlos a todos (hasta MAX_CAMERA_ZOOM)
 pub fn camera_follow_system(
     time: Res<Time>,
     game_assets: Res<GameAssets>,
-    player_query: Query<&Transform, (With<PlayerCharacter>, Without<Camera>)>,
-    mut camera_query: Query<&mut Transform, (With<Camera>, Without<PlayerCharacter>)>,
+    player_query: Query<&GlobalTransform, With<PlayerCharacter>>,
+    mut camera_query: Query<(&mut Transform, &mut Projection), With<Camera>>,
     windows: Query<&Window>,
 ) {
-    if let Ok(player_transform) = player_query.single() {
-        if let Ok(mut camera_transform) = camera_query.single_mut() {
-            let target_pos = player_transform.translation;
-            let current_pos = camera_transform.translation;
-
-            // Suavizado mejorado para la cámara
-            let smoothing_factor = 6.0;
-            let t = (smoothing_factor * time.delta_secs()).min(1.0);
-
-            // Calcular nueva posición con suavizado
-            let smooth_x = current_pos.x + (target_pos.x - current_pos.x) * t;
-
-            // Obtener dimensiones de la ventana para calcular el viewport de la cámara
-            let Ok(window) = windows.single() else {
-                return;
-            };
-            let camera_half_width = window.width() / 2.0;
-
-            // Calcular dimensiones totales del mapa en píxeles
-            let map_width_px = game_assets.map_width_tiles as f32 * game_assets.tile_size_px;
-
-            // El mapa está centrado en el origen (0, 0), así que calculamos los bordes
-            let map_left = -(map_width_px / 2.0);
-            let map_right = map_width_px / 2.0;
-
-            // Calcular límites horizontales para el centro de la cámara
-            let camera_min_x = map_left + camera_half_width;
-            let camera_max_x = map_right - camera_half_width;
-
-            // Aplicar límites horizontales
-            let clamped_x = if camera_max_x <= camera_min_x {
-                // Si el mapa es más pequeño que el viewport de la cámara,
-                // centrar la cámara horizontalmente en el mapa
-                (map_left + map_right) / 2.0
-            } else {
-                // Caso normal: limitar la posición de la cámara a los bordes calculados
-                smooth_x.clamp(camera_min_x, camera_max_x)
-            };
-
-            // Aplicar las posiciones finales con límites horizontales
-            camera_transform.translation.x = clamped_x;
-            // Mantener Y fijo o seguir al jugador sin límites verticales
-            // camera_transform.translation.y = current_pos.y + (target_pos.y - current_pos.y) * t;
-        }
+    let Some((min_x, max_x)) = player_query.iter().fold(None, |bounds, transform| {
+        let x = transform.translation().x;
+        Some(bounds.map_or((x, x), |(min, max): (f32, f32)| (min.min(x), max.max(x))))
+    }) else {
+        return;
+    };
+    let Ok((mut camera_transform, mut projection)) = camera_query.single_mut() else {
+        return;
+    };
+    // Obtener dimensiones de la ventana para calcular el viewport de la cámara
+    let Ok(window) = windows.single() else {
+        return;
+    };
+
+    let target_x = (min_x + max_x) / 2.0;
+    let current_pos = camera_transform.translation;
+
+    // Suavizado mejorado para la cámara
+    let smoothing_factor = 6.0;
+    let t = (smoothing_factor * time.delta_secs()).min(1.0);
+
+    // Zoom necesario para que quepan todos los players con margen a los lados
+    let target_zoom =
+        ((max_x - min_x + 2.0 * CAMERA_PLAYER_MARGIN) / window.width()).clamp(1.0, MAX_CAMERA_ZOOM);
+    let mut zoom = 1.0;
+    if let Projection::Orthographic(orthographic) = projection.as_mut() {
+        orthographic.scale += (target_zoom - orthographic.scale) * t;
+        zoom = orthographic.scale;
     }
+
+    // Calcular nueva posición con suavizado
+    let smooth_x = current_pos.x + (target_x - current_pos.x) * t;
+
+    let camera_half_width = window.width() / 2.0 * zoom;
+
+    // Calcular dimensiones totales del mapa en píxeles
+    let map_width_px = game_assets.map_width_tiles as f32 * game_assets.tile_size_px;
+
+    // El mapa está centrado en el origen (0, 0), así que calculamos los bordes
+    let map_left = -(map_width_px / 2.0);
+    let map_right = map_width_px / 2.0;
+
+    // Calcular límites horizontales para el centro de la cámara
+    let camera_min_x = map_left + camera_half_width;
+    let camera_max_x = map_right - camera_half_width;
+
+    // Aplicar límites horizontales
+    let clamped_x = if camera_max_x <= camera_min_x {
+        // Si el mapa es más pequeño que el viewport de la cámara,
+        // centrar la cámara horizontalmente en el mapa
+        (map_left + map_right) / 2.0
+    } else {
+        // Caso normal: limitar la posición de la cámara a los bordes calculados
+        smooth_x.clamp(camera_min_x, camera_max_x)
+    };
+
+    // Aplicar las posiciones finales con límites horizontales
+    camera_transform.translation.x = clamped_x;
+    // Mantener Y fijo o seguir al jugador sin límites verticales
+    // camera_transform.translation.y = current_pos.y + (target_pos.y - current_pos.y) * t;
 }
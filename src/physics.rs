@@ -7,6 +7,10 @@ pub const SMOOTHING_FACTOR: f32 = 0.9;
 #[derive(Component)]
 pub struct AffectedByGravity;
 
+// Mientras lo tenga, la entidad no acumula gravedad (p. ej. durante un dash)
+#[derive(Component)]
+pub struct IgnoreGravity;
+
 #[derive(Component, Debug)]
 pub struct Mass {
     pub kilograms: f32,
@@ -28,13 +32,19 @@ pub struct Velocity {
 pub fn gravity_system(
     time: Res<Time>,
     mut query: Query<
-        (&mut Velocity, &Mass, &KinematicCharacterControllerOutput),
+        (
+            Entity,
+            &mut Velocity,
+            &Mass,
+            &KinematicCharacterControllerOutput,
+        ),
         With<AffectedByGravity>,
     >,
+    ignore_gravity_query: Query<(), With<IgnoreGravity>>,
 ) {
     let t = (SMOOTHING_FACTOR * time.delta_secs()).min(1.0);
-    for (mut velocity, mass, output) in &mut query {
-        if !output.grounded {
+    for (entity, mut velocity, mass, output) in &mut query {
+        if !output.grounded && !ignore_gravity_query.contains(entity) {
             let gravity_force = GRAVITY * mass.kilograms;
             velocity.velocity.y -= gravity_force * t; // acumula
         }
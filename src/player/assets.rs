@@ -3,15 +3,20 @@ use std::fs;
 use bevy::prelude::*;
 use serde::Deserialize;
 
-use crate::map::components::CurrentLevelInfo;
+use crate::{
+    characters::components::{CharacterSheets, GameCharacters},
+    map::components::CurrentLevelInfo,
+    player::components::{MovementTuning, PlayableCharacter, PlayableCharacters},
+};
+
+const MOVEMENT_TUNING_PATH: &str = "assets/player/movement_tuning.json";
 
 // Un Resource para contener las handles de los assets del jugador
 #[derive(Resource)]
 pub struct PlayerAssets {
     pub hearts: Handle<Image>,
-    pub texture_standing: Handle<Image>,
-    pub texture_left: Handle<Image>,
-    pub texture_right: Handle<Image>,
+    pub hearts_layout: Handle<TextureAtlasLayout>,
+    pub boost_icon: Handle<Image>,
     // Puedes añadir más assets si los necesitas, como sonidos, otras animaciones, etc.
 }
 
@@ -21,10 +26,64 @@ pub struct HeroData {
     pub y: f32,
 }
 
+// Carga los parámetros de movimiento; si el fichero falta o está mal, usa los de por defecto
+pub fn load_movement_tuning(mut commands: Commands) {
+    let tuning = match fs::read_to_string(MOVEMENT_TUNING_PATH) {
+        Ok(json) => serde_json::from_str(&json).unwrap_or_else(|err| {
+            warn!("Failed to parse {MOVEMENT_TUNING_PATH}: {err}");
+            MovementTuning::default()
+        }),
+        Err(err) => {
+            warn!("Failed to read {MOVEMENT_TUNING_PATH}: {err}");
+            MovementTuning::default()
+        }
+    };
+
+    commands.insert_resource(tuning);
+}
+
+// Carga los gatos de la pantalla de selección, los que tienen bloque `playable` en
+// game_characters.json, con su hoja y las estadísticas de su propia entrada
+pub fn load_playable_characters(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    game_characters: Res<GameCharacters>,
+    sheets: Res<CharacterSheets>,
+) {
+    let mut playable = Vec::new();
+    for (key, character) in game_characters.0.iter() {
+        let Some(data) = &character.playable else {
+            continue;
+        };
+        let (Some(stats), Some(sheet)) = (character.stats, sheets.get(key)) else {
+            warn!(
+                "Playable character '{key}' needs a sheet and stats in game characters JSON, skipping it"
+            );
+            continue;
+        };
+        playable.push((data, stats, sheet));
+    }
+    playable.sort_by_key(|(data, _, _)| data.order);
+
+    let characters = playable
+        .into_iter()
+        .map(|(data, stats, sheet)| PlayableCharacter {
+            name: data.name.clone(),
+            sheet: sheet.clone(),
+            projectile: asset_server.load(data.projectile.image_path()),
+            projectile_type: data.projectile,
+            character_stats: stats,
+        })
+        .collect();
+
+    commands.insert_resource(PlayableCharacters(characters));
+}
+
 pub fn load_player_assets(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     level_info: Res<CurrentLevelInfo>,
+    mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
 ) {
     let hero_data: HeroData = serde_json::from_str(
         &fs::read_to_string(level_info.data.player.clone()).expect("Failed to read level JSON"),
@@ -33,10 +92,14 @@ pub fn load_player_assets(
 
     commands.insert_resource(PlayerAssets {
         hearts: asset_server.load("player/Corazon-Sheet.png"),
-        texture_standing: asset_server
-            .load("characters/tofe/standing/Sprite-tofe-standing-Sheet.png"),
-        texture_left: asset_server.load("characters/tofe/walking/Sprite-tofe-walking-L-Sheet.png"),
-        texture_right: asset_server.load("characters/tofe/walking/Sprite-tofe-walking-R-Sheet.png"),
+        hearts_layout: texture_atlas_layouts.add(TextureAtlasLayout::from_grid(
+            UVec2::splat(160),
+            21,
+            1,
+            None,
+            None,
+        )),
+        boost_icon: asset_server.load("treat.png"),
     });
     commands.insert_resource(hero_data);
 }
@@ -1,4 +1,5 @@
 use crate::{
+    characters::components::CharacterStats,
     physics::AffectedByGravity,
     player::components::{Health, PlayerCharacter},
 };
@@ -15,11 +16,11 @@ pub struct PlayerBundle {
 }
 
 impl PlayerBundle {
-    pub fn new(transform: Transform) -> Self {
+    pub fn new(transform: Transform, stats: &CharacterStats) -> Self {
         Self {
             transform: transform,
             player_character: PlayerCharacter,
-            health: Health::default(),
+            health: Health::full(stats.health),
             global_transform: GlobalTransform::default(),
             visibility: Visibility::default(),
             gravity: AffectedByGravity,
@@ -1,59 +1,255 @@
 use std::time::Duration;
 
 use bevy::prelude::*;
+use serde::Deserialize;
+
+use crate::characters::components::{CharacterSheet, CharacterStats};
 
 // Componente principal para el personaje
 #[derive(Component)]
 pub struct PlayerCharacter;
 
+/// Jugador local al que pertenece la entidad: 0 es el jugador 1 y 1 el jugador 2 (co-op).
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PlayerId(pub usize);
+
+pub const MAX_PLAYERS: usize = 2;
+
+/// Número de jugadores de la partida, elegido en el menú principal.
+#[derive(Resource, Debug, Clone, Copy)]
+pub struct PlayerCount(pub usize);
+
+impl Default for PlayerCount {
+    fn default() -> Self {
+        Self(1)
+    }
+}
+
+impl PlayerCount {
+    pub fn is_coop(&self) -> bool {
+        self.0 > 1
+    }
+}
+
+/// El player se ha quedado sin corazones y espera a que su compañero lo reanime
+/// quedándose cerca de él. Si caen todos los players se pierde una vida.
 #[derive(Component)]
-pub struct CharacterLeftSprite;
+pub struct Downed {
+    pub revive: Timer,
+}
+
+impl Downed {
+    pub fn new(revive_time: f32) -> Self {
+        Self {
+            revive: Timer::from_seconds(revive_time, TimerMode::Once),
+        }
+    }
+}
+
+// Texto sobre el player caído con el progreso de la reanimación
+#[derive(Component)]
+pub struct ReviveText;
+
+/// El player se está deslizando por una pared. `direction` es 1.0 si la pared está a la
+/// derecha y -1.0 si está a la izquierda.
 #[derive(Component)]
-pub struct CharacterRightSprite;
+pub struct WallSlide {
+    pub direction: f32,
+}
+
+/// El player está colgado de un borde. `direction` apunta hacia la pared, como en
+/// `WallSlide`; `top` es dónde queda al trepar y `climb` el trepado en curso.
 #[derive(Component)]
-pub struct CharacterIdleSprite;
+pub struct LedgeHang {
+    pub direction: f32,
+    pub hang: Vec2,
+    pub top: Vec2,
+    pub climb: Option<Timer>,
+}
 
+/// Vida del player, contada en medios corazones.
 #[derive(Component)]
 pub struct Health {
     pub current: u32,
     pub max: u32,
 }
 
-impl Default for Health {
+pub const HALF_HEARTS_PER_HEART: u32 = 2;
+
+impl Health {
+    pub fn full(max: u32) -> Self {
+        Self { current: max, max }
+    }
+
+    // Contenedores de corazón que se ven en el HUD
+    pub fn hearts(&self) -> u32 {
+        self.max.div_ceil(HALF_HEARTS_PER_HEART)
+    }
+}
+
+/// Vidas de la partida. Vaciar los corazones cuesta una vida; sin vidas, game over.
+#[derive(Resource)]
+pub struct Lives {
+    pub current: u32,
+}
+
+impl Default for Lives {
     fn default() -> Self {
-        Self { current: 6, max: 6 }
+        Self { current: 3 }
+    }
+}
+
+/// Proyectil que lanza cada gato. Cargando el lanzamiento la velocidad sube del mínimo
+/// al máximo; el rebote es la restitución contra los tiles.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub enum ProjectileType {
+    Wool,
+    RedWool,    // Más rápido y casi sin rebote
+    RubberBall, // Más lento pero rebota mucho
+}
+
+impl ProjectileType {
+    pub fn image_path(self) -> &'static str {
+        match self {
+            ProjectileType::Wool => "projectiles/wool.png",
+            ProjectileType::RedWool => "projectiles/wool3.png",
+            ProjectileType::RubberBall => "projectiles/projectile.png",
+        }
+    }
+
+    // Velocidad de salida sin cargar y con la carga completa
+    pub fn speed_range(self) -> (f32, f32) {
+        match self {
+            ProjectileType::Wool => (600.0, 1000.0),
+            ProjectileType::RedWool => (750.0, 1250.0),
+            ProjectileType::RubberBall => (500.0, 900.0),
+        }
+    }
+
+    pub fn restitution(self) -> f32 {
+        match self {
+            ProjectileType::Wool => 0.3,
+            ProjectileType::RedWool => 0.1,
+            ProjectileType::RubberBall => 0.8,
+        }
+    }
+
+    // Velocidad para una carga entre 0 y 1
+    pub fn speed(self, charge: f32) -> f32 {
+        let (min, max) = self.speed_range();
+        min.lerp(max, charge.clamp(0.0, 1.0))
+    }
+}
+
+/// Bloque `playable` de los gatos que se pueden elegir en `assets/game_characters.json`.
+#[derive(Debug, Deserialize)]
+pub struct PlayableCharacterData {
+    pub name: String,
+    pub order: usize, // Posición en la pantalla de selección
+    pub projectile: ProjectileType,
+}
+
+/// Gato jugable con su hoja ya cargada.
+pub struct PlayableCharacter {
+    pub name: String,
+    pub sheet: CharacterSheet,
+    pub projectile: Handle<Image>,
+    pub projectile_type: ProjectileType,
+    // Estadísticas del bloque `stats` de la entrada del gato
+    pub character_stats: CharacterStats,
+}
+
+#[derive(Resource)]
+pub struct PlayableCharacters(pub Vec<PlayableCharacter>);
+
+impl PlayableCharacters {
+    pub fn get(&self, index: usize) -> &PlayableCharacter {
+        self.0.get(index).unwrap_or(&self.0[0])
     }
 }
 
-// Componente AnimationIndices (igual que antes)
+// Índice del gato elegido por cada jugador en la pantalla de selección, en orden de PlayerId
+#[derive(Resource, Default)]
+pub struct SelectedCharacters(pub Vec<usize>);
+
+impl SelectedCharacters {
+    pub fn get(&self, player: PlayerId) -> usize {
+        self.0.get(player.0).copied().unwrap_or_default()
+    }
+
+    // Un player por gato elegido; sin selección juega solo el jugador 1 con el primer gato
+    pub fn players(&self) -> Vec<(PlayerId, usize)> {
+        if self.0.is_empty() {
+            return vec![(PlayerId(0), 0)];
+        }
+        self.0
+            .iter()
+            .take(MAX_PLAYERS)
+            .enumerate()
+            .map(|(player, &character)| (PlayerId(player), character))
+            .collect()
+    }
+}
+
+// Proyectil que lanza el player y su imagen
 #[derive(Component)]
-pub struct AnimationIndices {
-    pub first: usize,
-    pub last: usize,
-    pub fps: u8,
-    pub frame_timer: Timer,
+pub struct PlayerProjectile {
+    pub image: Handle<Image>,
+    pub projectile_type: ProjectileType,
 }
 
-impl AnimationIndices {
-    pub fn new(first: usize, last: usize, fps: u8) -> Self {
+/// Bolas de lana que lleva el player y enfriamiento entre lanzamientos.
+#[derive(Component)]
+pub struct WoolAmmo {
+    pub current: u32,
+    pub max: u32,
+    pub cooldown: Timer,
+}
+
+impl WoolAmmo {
+    pub fn new(max: u32, cooldown: f32) -> Self {
         Self {
-            first,
-            last,
-            fps,
-            frame_timer: Timer::new(
-                Duration::from_secs_f32(1.0 / (fps as f32)),
-                TimerMode::Repeating,
-            ),
+            current: max,
+            max,
+            cooldown: finished_timer(cooldown),
         }
     }
-    pub fn timer_from_fps(fps: u8) -> Timer {
-        Timer::new(
-            Duration::from_secs_f32(1.0 / (fps as f32)),
-            TimerMode::Repeating,
-        )
+
+    pub fn can_throw(&self) -> bool {
+        self.current > 0 && self.cooldown.finished()
+    }
+
+    pub fn refill(&mut self, amount: u32) {
+        self.current = (self.current + amount).min(self.max);
     }
 }
 
+/// Modo de lanzamiento del player: arco alto o bajo, y la carga (de 0 a 1) que acumula
+/// mientras mantiene pulsado el botón de lanzar.
+#[derive(Component, Default)]
+pub struct ThrowMode {
+    pub high_arc: bool,
+    pub charging: bool,
+    pub charge: f32,
+}
+
+// Marcadores del contador de lana en el HUD
+#[derive(Component)]
+pub struct WoolHud;
+
+#[derive(Component)]
+pub struct WoolHudText(pub PlayerId);
+
+#[derive(Component)]
+pub struct ThrowChargeHudFill(pub PlayerId);
+
+// Marcadores del contador de vidas en el HUD
+#[derive(Component)]
+pub struct LivesHud;
+
+#[derive(Component)]
+pub struct LivesHudText;
+
 // Componente para el doble salto
 #[derive(Component)]
 pub struct DoubleJump {
@@ -70,9 +266,153 @@ impl Default for DoubleJump {
     }
 }
 
+/// Ventanas de tolerancia del salto: coyote time (saltar poco después de dejar el suelo)
+/// y jump buffer (pulsar salto poco antes de aterrizar).
+///
+/// Ambos temporizadores empiezan terminados; el de coyote se rearma mientras el player
+/// está en el suelo y el del buffer al pulsar salto.
+#[derive(Component)]
+pub struct JumpTiming {
+    pub coyote: Timer,
+    pub buffer: Timer,
+}
+
+impl JumpTiming {
+    pub fn new(coyote_time: f32, buffer_time: f32) -> Self {
+        Self {
+            coyote: finished_timer(coyote_time),
+            buffer: finished_timer(buffer_time),
+        }
+    }
+
+    /// Avanza las dos ventanas un frame. Devuelve `true` si el coyote time se acaba de
+    /// agotar en el aire.
+    pub fn tick(&mut self, delta: Duration, on_ground: bool, jump_just_pressed: bool) -> bool {
+        // Coyote time: se rearma en el suelo y corre al dejarlo
+        let coyote_expired = if on_ground {
+            self.coyote.reset();
+            false
+        } else {
+            self.coyote.tick(delta);
+            self.coyote.just_finished()
+        };
+
+        // Jump buffer: la pulsación se recuerda durante un momento
+        if jump_just_pressed {
+            self.buffer.reset();
+        } else {
+            self.buffer.tick(delta);
+        }
+
+        coyote_expired
+    }
+
+    pub fn in_coyote_time(&self) -> bool {
+        !self.coyote.finished()
+    }
+
+    pub fn jump_buffered(&self) -> bool {
+        !self.buffer.finished()
+    }
+
+    /// Gasta las dos ventanas al saltar, para que el mismo salto no se repita.
+    pub fn consume(&mut self) {
+        consume_timer(&mut self.coyote);
+        consume_timer(&mut self.buffer);
+    }
+}
+
+fn consume_timer(timer: &mut Timer) {
+    let remaining = timer.remaining();
+    timer.tick(remaining);
+}
+
+/// Dash del player: enfriamiento, si queda dash en el aire y hacia dónde mira.
+#[derive(Component)]
+pub struct Dash {
+    pub cooldown: Timer,
+    pub air_dash_available: bool,
+    pub facing: f32,
+}
+
+impl Dash {
+    pub fn new(cooldown: f32) -> Self {
+        Self {
+            cooldown: finished_timer(cooldown),
+            air_dash_available: true,
+            facing: 1.0,
+        }
+    }
+}
+
+/// Dash en curso: velocidad fija sin gravedad hasta que termine el temporizador.
+#[derive(Component)]
+pub struct Dashing {
+    pub velocity: Vec2,
+    pub timer: Timer,
+}
+
+// Marcadores de la barra de enfriamiento del dash en el HUD
+#[derive(Component)]
+pub struct DashHud;
+
+#[derive(Component)]
+pub struct DashHudFill(pub PlayerId);
+
+// Temporizador que empieza ya terminado, para habilidades disponibles desde el principio
+fn finished_timer(duration: f32) -> Timer {
+    let mut timer = Timer::from_seconds(duration, TimerMode::Once);
+    timer.tick(timer.duration());
+    timer
+}
+
+/// Parámetros del movimiento del player, cargados de `assets/player/movement_tuning.json`.
+///
+/// Las velocidades van en las mismas unidades que `physics::Velocity` y las
+/// aceleraciones en esas unidades por segundo.
+#[derive(Resource, Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct MovementTuning {
+    pub ground_acceleration: f32,
+    pub ground_deceleration: f32,
+    pub air_acceleration: f32,
+    pub air_deceleration: f32,
+    // Al soltar salto mientras se sube, la velocidad vertical se multiplica por esto
+    pub jump_cut_multiplier: f32,
+    pub coyote_time: f32,
+    pub jump_buffer_time: f32,
+    // Velocidad máxima de caída deslizando por una pared
+    pub wall_slide_speed: f32,
+    // Velocidad horizontal con la que el salto de pared aleja de ella
+    pub wall_jump_push: f32,
+    // Distancia fija que recorre el dash, en píxeles, y cuánto dura
+    pub dash_distance: f32,
+    pub dash_duration: f32,
+    pub dash_cooldown: f32,
+    pub dash_invincibility: f32,
+}
+
+impl Default for MovementTuning {
+    fn default() -> Self {
+        Self {
+            ground_acceleration: 2000.0,
+            ground_deceleration: 2400.0,
+            air_acceleration: 1000.0,
+            air_deceleration: 500.0,
+            jump_cut_multiplier: 0.45,
+            coyote_time: 0.1,
+            jump_buffer_time: 0.12,
+            wall_slide_speed: 120.0,
+            wall_jump_push: 300.0,
+            dash_distance: 120.0,
+            dash_duration: 0.15,
+            dash_cooldown: 0.8,
+            dash_invincibility: 0.25,
+        }
+    }
+}
+
 pub const GRAVITY: f32 = 9.81; // m/s² - aceleración gravitacional terrestre
-pub const JUMP_FORCE: f32 = 500.0; // Newtons - fuerza de salto hacia arriba
-pub const HORIZONTAL_FORCE: f32 = 200.0; // Newtons - fuerza de movimiento lateral
 
 #[derive(Component)]
 pub struct Invincibility {
@@ -87,13 +427,165 @@ impl Invincibility {
     }
 }
 
+/// Se emite cuando un player recibe daño. `direction` apunta desde la fuente del daño
+/// hacia el player y decide hacia qué lado sale despedido.
+#[derive(Event)]
+pub struct PlayerHit {
+    pub player: Entity,
+    pub direction: Vec2,
+}
+
+/// Destello del sprite justo después de recibir daño.
+#[derive(Component)]
+pub struct HitFlash {
+    pub timer: Timer,
+}
+
+/// Empujón tras recibir daño; mientras dura, el player no controla su movimiento.
+#[derive(Component)]
+pub struct Knockback {
+    pub timer: Timer,
+}
+
+/// Parón breve del juego al recibir un golpe (hit-stop). Se descuenta con tiempo real
+/// porque el tiempo virtual está pausado mientras dura.
+#[derive(Resource)]
+pub struct HitStop {
+    pub timer: Timer,
+}
+
+/// Boost temporal de velocidad y salto (BoneCookie).
+///
+/// Coger otra galleta con el boost activo no acumula multiplicadores: se queda el
+/// multiplicador más alto y el tiempo restante pasa a ser el mayor de los dos.
+#[derive(Component)]
+pub struct SpeedBoost {
+    pub multiplier: f32,
+    pub timer: Timer,
+}
+
+impl SpeedBoost {
+    pub fn new(duration: f32, multiplier: f32) -> Self {
+        Self {
+            multiplier,
+            timer: Timer::from_seconds(duration, TimerMode::Once),
+        }
+    }
+
+    pub fn refresh(&mut self, duration: f32, multiplier: f32) {
+        self.multiplier = self.multiplier.max(multiplier);
+        if duration > self.timer.remaining_secs() {
+            self.timer = Timer::from_seconds(duration, TimerMode::Once);
+        }
+    }
+}
+
+// Marcadores del icono del boost en el HUD
+#[derive(Component)]
+pub struct SpeedBoostHud(pub PlayerId);
+
+#[derive(Component)]
+pub struct SpeedBoostHudText(pub PlayerId);
+
+/// Corazón del HUD de un player. El sprite avanza o retrocede fotograma a fotograma hasta
+/// `target_frame`, así que se anima tanto al perder vida como al recuperarla.
 #[derive(Component)]
 pub struct PlayerHearts {
     pub idx: usize,
+    pub player: PlayerId,
+    pub target_frame: usize,
+    pub frame_timer: Timer,
 }
 
 impl PlayerHearts {
-    pub fn new(idx: usize) -> Self {
-        Self { idx: idx }
+    pub fn new(idx: usize, player: PlayerId, target_frame: usize, fps: u8) -> Self {
+        Self {
+            idx: idx,
+            player,
+            target_frame,
+            frame_timer: Timer::new(
+                Duration::from_secs_f32(1.0 / (fps as f32)),
+                TimerMode::Repeating,
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FRAME: Duration = Duration::from_millis(50);
+
+    #[test]
+    fn speed_boost_refresh_keeps_the_best_of_both() {
+        let mut boost = SpeedBoost::new(5.0, 1.5);
+        boost.timer.tick(Duration::from_secs(3));
+
+        // Una galleta más corta y más débil no cambia nada
+        boost.refresh(1.0, 1.2);
+        assert_eq!(boost.multiplier, 1.5);
+        assert!((boost.timer.remaining_secs() - 2.0).abs() < 1e-4);
+
+        // Una más larga reinicia el tiempo, pero no baja el multiplicador
+        boost.refresh(4.0, 1.2);
+        assert_eq!(boost.multiplier, 1.5);
+        assert!((boost.timer.remaining_secs() - 4.0).abs() < 1e-4);
+
+        // Una más fuerte sube el multiplicador sin acortar el tiempo
+        boost.refresh(1.0, 2.0);
+        assert_eq!(boost.multiplier, 2.0);
+        assert!((boost.timer.remaining_secs() - 4.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn windows_start_closed() {
+        let timing = JumpTiming::new(0.1, 0.1);
+
+        assert!(!timing.in_coyote_time());
+        assert!(!timing.jump_buffered());
+    }
+
+    #[test]
+    fn coyote_time_runs_out_after_leaving_the_ground() {
+        let mut timing = JumpTiming::new(0.1, 0.1);
+
+        assert!(!timing.tick(FRAME, true, false));
+        assert!(timing.in_coyote_time());
+
+        assert!(!timing.tick(FRAME, false, false));
+        assert!(timing.in_coyote_time());
+
+        // Solo avisa una vez, el frame en que se agota
+        assert!(timing.tick(FRAME, false, false));
+        assert!(!timing.in_coyote_time());
+        assert!(!timing.tick(FRAME, false, false));
+    }
+
+    #[test]
+    fn jump_buffer_remembers_the_press() {
+        let mut timing = JumpTiming::new(0.1, 0.1);
+
+        timing.tick(FRAME, false, true);
+        assert!(timing.jump_buffered());
+
+        timing.tick(FRAME, false, false);
+        assert!(timing.jump_buffered());
+
+        timing.tick(FRAME, false, false);
+        assert!(!timing.jump_buffered());
+    }
+
+    #[test]
+    fn consume_closes_both_windows() {
+        let mut timing = JumpTiming::new(0.1, 0.1);
+        timing.tick(FRAME, true, true);
+
+        timing.consume();
+
+        assert!(!timing.in_coyote_time());
+        assert!(!timing.jump_buffered());
+        // Gastarlo no cuenta como que el coyote time se haya agotado solo
+        assert!(!timing.tick(FRAME, false, false));
     }
 }
@@ -5,22 +5,28 @@ pub mod bundle; // Declara el submódulo bundle.rs
 pub mod components; // Declara el submódulo components.rs
 pub mod systems; // Declara el submódulo systems.rs // Declara el submódulo assets.rs
 
-use crate::game_state::GameState;
+use crate::animation::{
+    components::{CharacterAnimator, CharacterSprite},
+    systems::animate_characters,
+};
+use crate::characters::assets::{CHARACTER_SPRITE_SIZE, load_character_sheets};
+use crate::game_state::{GameState, is_pause_transition};
 use crate::map::ONE_WAY_PLATFORM_GROUP;
 use crate::map::assets::GameAssets;
 use crate::physics::{AffectedByGravity, Mass, Velocity};
-use crate::player::assets::{HeroData, load_player_assets};
+use crate::player::assets::{
+    HeroData, load_movement_tuning, load_playable_characters, load_player_assets,
+};
 
 use crate::player::{
-    assets::PlayerAssets, // Importa PlayerAssets
     bundle::PlayerBundle, // Importa el PlayerBundle
     components::*,        // Importa todos los componentes del player
     systems::*,           // Importa todos los sistemas del player
 };
 use bevy::prelude::*;
 use bevy_rapier2d::prelude::{
-    ActiveEvents, Collider, CollisionGroups, Group, KinematicCharacterController, RigidBody,
-    Velocity as RapierVelocity,
+    ActiveCollisionTypes, ActiveEvents, Collider, CollisionGroups, Group,
+    KinematicCharacterController, RigidBody, Velocity as RapierVelocity,
 };
 
 // Esta función añadirá todos los sistemas del jugador a la aplicación
@@ -28,32 +34,84 @@ pub struct PlayerPlugin;
 
 impl Plugin for PlayerPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Startup, load_player_assets)
+        app.add_event::<PlayerHit>()
+            .init_resource::<Lives>()
+            .init_resource::<SelectedCharacters>()
+            .init_resource::<PlayerCount>()
+            .add_systems(
+                Startup,
+                (
+                    load_player_assets,
+                    load_movement_tuning,
+                    load_playable_characters.after(load_character_sheets),
+                ),
+            )
             .add_systems(
                 OnEnter(GameState::Game),
                 (
                     spawn_player_character.after(load_player_assets),
-                    spawn_player_hearts.after(spawn_player_character),
-                ),
+                    spawn_lives_hud.after(spawn_player_character),
+                    spawn_dash_hud,
+                    spawn_wool_hud.after(spawn_player_character),
+                )
+                    .run_if(not(is_pause_transition)),
             )
             .add_systems(
                 Update,
                 (
-                    character_input_handling, // Maneja la entrada y visibilidad de los sprites
-                    player_input_system,
-                    execute_animations,   // Anima los sprites visibles
+                    player_input_system.after(reset_jumps),
+                    update_player_animation.before(animate_characters),
                     player_bounds_system, // Aplica límites de mapa al player
                     reset_jumps,
-                    update_player_life,
-                    animate_hearts,
+                    wall_slide_detection_system.before(player_input_system),
+                    dash_system.after(player_input_system),
+                    update_dash_hud,
                     invincibility_system,
+                    speed_boost_system,
+                    update_speed_boost_hud,
+                    update_lives_hud,
                     check_player_death,
-                    handle_gameover_timer,
+                    handle_respawn_timer,
+                    revive_downed_players,
+                    update_revive_text,
+                    apply_player_hits.before(player_input_system),
+                    hit_reaction_timers_system,
+                    player_sprite_feedback_system.after(speed_boost_system),
+                )
+                    .run_if(in_state(GameState::Game)),
+            )
+            .add_systems(
+                Update,
+                (
+                    update_player_hearts,
+                    animate_hearts,
+                    update_lives_hud_position,
+                    update_wool_hud,
                 )
                     .run_if(in_state(GameState::Game)),
             )
-            .add_systems(OnExit(GameState::Game), despawn_player)
-            .add_systems(OnExit(GameState::Game), despawn_hearts);
+            .add_systems(
+                Update,
+                (ledge_grab_system, ledge_hang_system)
+                    .chain()
+                    .after(dash_system)
+                    .before(update_player_animation)
+                    .run_if(in_state(GameState::Game)),
+            )
+            // Sin condición de estado para que el hit-stop nunca deje el tiempo pausado
+            .add_systems(Update, hit_stop_system)
+            .add_systems(
+                OnExit(GameState::Game),
+                (
+                    despawn_player,
+                    despawn_hearts,
+                    despawn_speed_boost_hud,
+                    despawn_lives_hud,
+                    despawn_dash_hud,
+                    despawn_wool_hud,
+                )
+                    .run_if(not(is_pause_transition)),
+            );
     }
 }
 
@@ -67,68 +125,176 @@ fn despawn_hearts(mut commands: Commands, player_query: Query<Entity, With<Playe
         commands.entity(entity).despawn();
     }
 }
+fn despawn_speed_boost_hud(mut commands: Commands, query: Query<Entity, With<SpeedBoostHud>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+fn despawn_lives_hud(mut commands: Commands, query: Query<Entity, With<LivesHud>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+fn despawn_dash_hud(mut commands: Commands, query: Query<Entity, With<DashHud>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
+fn despawn_wool_hud(mut commands: Commands, query: Query<Entity, With<WoolHud>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn();
+    }
+}
 
-pub fn spawn_player_hearts(
+// Contador de lana de cada player, con la imagen de su proyectil, el arco elegido y la
+// carga del lanzamiento, debajo del dash
+pub fn spawn_wool_hud(
     mut commands: Commands,
-    player_assets: Res<PlayerAssets>,
-    player_query: Query<&Health, With<PlayerCharacter>>,
-    mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+    player_query: Query<(&PlayerId, &PlayerProjectile, &WoolAmmo, &ThrowMode)>,
 ) {
-    let Ok(player_health) = player_query.single() else {
-        println!("No player health");
+    for (player, projectile, ammo, throw_mode) in player_query.iter() {
+        commands.spawn((
+            WoolHud,
+            hud_node(*player, 110.0),
+            children![
+                (
+                    ImageNode {
+                        image: projectile.image.clone(),
+                        ..default()
+                    },
+                    Node {
+                        width: Val::Px(24.0),
+                        height: Val::Px(24.0),
+                        margin: UiRect::right(Val::Px(6.0)),
+                        ..default()
+                    },
+                ),
+                (
+                    Text::new(wool_hud_text(ammo, throw_mode)),
+                    WoolHudText(*player),
+                    Node {
+                        margin: UiRect::right(Val::Px(6.0)),
+                        ..default()
+                    },
+                ),
+                // Barra de carga del lanzamiento
+                (
+                    Node {
+                        width: Val::Px(60.0),
+                        height: Val::Px(8.0),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.5)),
+                    children![(
+                        ThrowChargeHudFill(*player),
+                        Node {
+                            width: Val::Percent(0.0),
+                            height: Val::Percent(100.0),
+                            ..default()
+                        },
+                        BackgroundColor(THROW_CHARGE_COLOR),
+                    )],
+                ),
+            ],
+        ));
+    }
+}
+
+// Barra de enfriamiento del dash de cada player, debajo del icono del boost
+pub fn spawn_dash_hud(mut commands: Commands, player_count: Res<PlayerCount>) {
+    for player in (0..player_count.0).map(PlayerId) {
+        commands.spawn((
+            DashHud,
+            hud_node(player, 90.0),
+            children![
+                (
+                    Text::new("Dash"),
+                    Node {
+                        margin: UiRect::right(Val::Px(6.0)),
+                        ..default()
+                    },
+                ),
+                (
+                    Node {
+                        width: Val::Px(60.0),
+                        height: Val::Px(8.0),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.5)),
+                    children![(
+                        DashHudFill(player),
+                        Node {
+                            width: Val::Percent(100.0),
+                            height: Val::Percent(100.0),
+                            ..default()
+                        },
+                        BackgroundColor(DASH_READY_COLOR),
+                    )],
+                ),
+            ],
+        ));
+    }
+}
+
+// Contador de vidas, compartidas en co-op, a la derecha de los corazones del jugador 1
+pub fn spawn_lives_hud(
+    mut commands: Commands,
+    characters: Res<PlayableCharacters>,
+    selected: Res<SelectedCharacters>,
+    lives: Res<Lives>,
+    player_query: Query<(&PlayerId, &Health), With<PlayerCharacter>>,
+) {
+    let Some((_, player_health)) = player_query.iter().find(|(player, _)| player.0 == 0) else {
         return;
     };
 
-    let layout = TextureAtlasLayout::from_grid(UVec2::splat(160), 21, 1, None, None);
-    let texture_atlas_layout = texture_atlas_layouts.add(layout);
-    let mut x_position = 14.0;
+    let sheet = &characters.get(selected.get(PlayerId(0))).sheet;
 
-    for idx in 0..player_health.max {
-        let heart_transform = Transform {
-            // translation: Vec3::new(x_position, 60.0, 10.0),
-            scale: Vec3::splat(0.25),
+    commands.spawn((
+        LivesHud,
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(7.0),
+            left: Val::Px(lives_hud_left(player_health)),
+            align_items: AlignItems::Center,
             ..default()
-        };
-
-        commands.spawn((
-            PlayerHearts::new(idx as usize),
-            ImageNode {
-                image: player_assets.hearts.clone(),
-                texture_atlas: Some(TextureAtlas {
-                    layout: texture_atlas_layout.clone(),
-                    index: 0,
-                }),
-                ..default()
-            },
-            Node {
-                position_type: PositionType::Absolute,
-                top: Val::Px(7.0),
-                left: Val::Px(x_position),
-                ..default()
-            },
-            heart_transform,
-        ));
-
-        x_position += 45.0;
-    }
+        },
+        children![
+            (
+                ImageNode {
+                    image: sheet.image.clone(),
+                    texture_atlas: Some(TextureAtlas {
+                        layout: sheet.layout.clone(),
+                        index: 0,
+                    }),
+                    ..default()
+                },
+                Node {
+                    width: Val::Px(40.0),
+                    height: Val::Px(40.0),
+                    ..default()
+                },
+            ),
+            (Text::new(format!("x {}", lives.current)), LivesHudText),
+        ],
+    ));
 }
 
 pub const PLAYER_GROUP: Group = Group::GROUP_1;
 
 fn spawn_player_character(
     mut commands: Commands,
-    player_assets: Res<PlayerAssets>, // Ahora obtenemos los assets precargados
-    mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+    characters: Res<PlayableCharacters>,
+    selected: Res<SelectedCharacters>, // Los gatos elegidos en la pantalla de selección
     hero_data: Res<HeroData>,
     game_assets: Res<GameAssets>,
+    tuning: Res<MovementTuning>,
 ) {
     let tile_size_from_json = game_assets.tile_size_px;
     let map_width_from_json = game_assets.map_width_tiles;
     let map_height_from_json = game_assets.map_height_tiles;
 
-    let layout = TextureAtlasLayout::from_grid(UVec2::splat(64), 8, 1, None, None);
-    let texture_atlas_layout = texture_atlas_layouts.add(layout);
-
     let x = hero_data.x as f32;
     let y = hero_data.y as f32;
 
@@ -142,72 +308,61 @@ fn spawn_player_character(
     transform.translation.x = world_x + tile_size_from_json / 2.0;
     transform.translation.y = world_y - tile_size_from_json / 2.0;
 
-    let character_controller = KinematicCharacterController {
-        filter_groups: Some(CollisionGroups {
-            memberships: PLAYER_GROUP,
-            filters: Group::ALL & !ONE_WAY_PLATFORM_GROUP,
-        }),
-        ..default()
-    };
+    // En co-op el jugador 2 aparece un poco a la derecha del 1
+    for (player, character_index) in selected.players() {
+        let character = characters.get(character_index);
+        let mut transform = transform;
+        transform.translation.x += player.0 as f32 * PLAYER_SPAWN_SPACING;
 
-    commands
-        .spawn(PlayerBundle::new(transform))
-        .with_children(|parent| {
-            parent.spawn((
-                Sprite {
-                    image: player_assets.texture_left.clone(),
-                    texture_atlas: Some(TextureAtlas {
-                        layout: texture_atlas_layout.clone(),
-                        index: 0,
-                    }),
-                    ..default()
-                },
-                Transform::from_scale(Vec3::splat(0.6)),
-                CharacterLeftSprite,
-                Visibility::Hidden,
-                AnimationIndices::new(0, 7, ANIMATION_FPS),
-            ));
-            parent.spawn((
-                Sprite {
-                    image: player_assets.texture_right.clone(),
-                    texture_atlas: Some(TextureAtlas {
-                        layout: texture_atlas_layout.clone(),
-                        index: 0,
-                    }),
-                    ..default()
-                },
-                Transform::from_scale(Vec3::splat(0.6)),
-                CharacterRightSprite,
-                Visibility::Hidden,
-                AnimationIndices::new(0, 7, ANIMATION_FPS),
-            ));
-            parent.spawn((
-                Sprite {
-                    image: player_assets.texture_standing.clone(),
-                    texture_atlas: Some(TextureAtlas {
-                        layout: texture_atlas_layout.clone(),
-                        index: 0,
-                    }),
-                    ..default()
-                },
-                Transform::from_scale(Vec3::splat(0.6)),
-                CharacterIdleSprite,
-                Visibility::Visible,
-                AnimationIndices::new(0, 7, ANIMATION_FPS),
-            ));
-        })
-        .insert(RigidBody::KinematicPositionBased)
-        .insert(character_controller)
-        .insert(Collider::ball(32.0 / 2.0))
-        .insert(PlayerCharacter)
-        .insert(AffectedByGravity)
-        .insert(RapierVelocity::zero())
-        .insert(Mass::default())
-        .insert(Health::default())
-        .insert(DoubleJump::default())
-        .insert(Velocity::default())
-        .insert(ActiveEvents::COLLISION_EVENTS);
-}
+        let character_controller = KinematicCharacterController {
+            filter_groups: Some(CollisionGroups {
+                memberships: PLAYER_GROUP,
+                filters: Group::ALL & !ONE_WAY_PLATFORM_GROUP,
+            }),
+            ..default()
+        };
 
-// Puedes definir constantes aquí o en un submódulo de constantes si tienes muchas
-const ANIMATION_FPS: u8 = 10;
+        commands
+            .spawn(PlayerBundle::new(transform, &character.character_stats))
+            .with_children(|parent| {
+                parent.spawn((
+                    Sprite {
+                        image: character.sheet.image.clone(),
+                        texture_atlas: Some(TextureAtlas {
+                            layout: character.sheet.layout.clone(),
+                            index: 0,
+                        }),
+                        custom_size: Some(Vec2::splat(CHARACTER_SPRITE_SIZE)),
+                        ..default()
+                    },
+                    Transform::from_scale(Vec3::splat(0.6)),
+                    CharacterSprite,
+                ));
+            })
+            .insert(CharacterAnimator::for_cat())
+            .insert(RigidBody::KinematicPositionBased)
+            .insert(character_controller)
+            .insert(Collider::ball(32.0 / 2.0))
+            .insert(PlayerCharacter)
+            .insert(player)
+            .insert(AffectedByGravity)
+            .insert(RapierVelocity::zero())
+            .insert(Mass {
+                kilograms: character.character_stats.mass,
+            })
+            .insert(character.character_stats)
+            .insert(PlayerProjectile {
+                image: character.projectile.clone(),
+                projectile_type: character.projectile_type,
+            })
+            .insert(ThrowMode::default())
+            .insert(DoubleJump::default())
+            .insert(JumpTiming::new(tuning.coyote_time, tuning.jump_buffer_time))
+            .insert(Dash::new(tuning.dash_cooldown))
+            .insert(WoolAmmo::new(WOOL_AMMO_MAX, THROW_COOLDOWN))
+            .insert(Velocity::default())
+            .insert(ActiveEvents::COLLISION_EVENTS)
+            // Player y enemigos son cinemáticos: sin esto su contacto no genera eventos
+            .insert(ActiveCollisionTypes::default() | ActiveCollisionTypes::KINEMATIC_KINEMATIC);
+    }
+}
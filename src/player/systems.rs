@@ -1,202 +1,829 @@
 use crate::{
+    animation::components::{AnimationState, CharacterAnimator, CharacterSprite},
+    characters::components::CharacterStats,
+    checkpoints::components::RespawnPoint,
     game_state::GameState,
-    map::assets::GameAssets,
-    physics::Velocity,
+    input::components::{Action, PlayerActions},
+    map::{ONE_WAY_PLATFORM_GROUP, assets::GameAssets, collider_bounds},
+    physics::{IgnoreGravity, SMOOTHING_FACTOR, Velocity},
     player::{
-        ANIMATION_FPS,
+        assets::PlayerAssets,
         components::{
-            AnimationIndices, CharacterIdleSprite, CharacterLeftSprite, CharacterRightSprite,
-            DoubleJump, HORIZONTAL_FORCE, Health, Invincibility, JUMP_FORCE, PlayerCharacter,
-            PlayerHearts,
+            Dash, DashHudFill, Dashing, DoubleJump, Downed, HALF_HEARTS_PER_HEART, Health,
+            HitFlash, HitStop, Invincibility, JumpTiming, Knockback, LedgeHang, Lives, LivesHud,
+            LivesHudText, MovementTuning, PlayerCharacter, PlayerHearts, PlayerHit, PlayerId,
+            ReviveText, SpeedBoost, SpeedBoostHud, SpeedBoostHudText, ThrowChargeHudFill,
+            ThrowMode, WallSlide, WoolAmmo, WoolHudText,
         },
     },
 };
 use bevy::prelude::*;
-use bevy_rapier2d::prelude::KinematicCharacterControllerOutput;
+use bevy_rapier2d::prelude::{
+    Collider, CollisionGroups, Group, KinematicCharacterControllerOutput, QueryFilter,
+    ReadRapierContext,
+};
 
 #[derive(Resource)]
-pub struct GameOverTimer(Timer);
+pub struct RespawnTimer(Timer);
+
+// Bolas de lana con las que empieza cada player y tiempo mínimo entre lanzamientos
+pub const WOOL_AMMO_MAX: u32 = 8;
+pub const THROW_COOLDOWN: f32 = 0.35;
+const WOOL_EMPTY_COLOR: Color = Color::srgb(1.0, 0.4, 0.4);
+
+const RESPAWN_DELAY: f32 = 1.0;
+const RESPAWN_INVINCIBILITY: f32 = 2.0;
+// Por debajo de esta velocidad horizontal el player se ve quieto
+const WALK_ANIMATION_MIN_SPEED: f32 = 5.0;
+// Una normal con menos componente vertical que esto se considera pared
+const WALL_NORMAL_MAX_Y: f32 = 0.3;
+// Agarre de bordes: alcance de los rayos más allá del collider, altura sobre la cabeza
+// desde la que se busca el borde, a qué altura bajo el borde se cuelga y cuánto tarda
+// en trepar (primero sube y luego avanza)
+const LEDGE_REACH: f32 = 6.0;
+const LEDGE_PROBE_HEIGHT: f32 = 8.0;
+const LEDGE_HANG_OFFSET: f32 = 6.0;
+const LEDGE_CLIMB_TIME: f32 = 0.3;
+const LEDGE_CLIMB_RISE: f32 = 0.6;
+// Distancia a la que un player reanima a su compañero caído, y cuánto tarda
+const REVIVE_RADIUS: f32 = 64.0;
+const REVIVE_TIME: f32 = 1.5;
+const REVIVE_INVINCIBILITY: f32 = 1.5;
+// Empujón al recibir daño: siempre hacia arriba y en sentido contrario a la fuente
+const KNOCKBACK_VELOCITY: Vec2 = Vec2::new(250.0, 250.0);
+const KNOCKBACK_TIME: f32 = 0.2;
+const HIT_FLASH_TIME: f32 = 0.12;
+const HIT_STOP_TIME: f32 = 0.06;
+// Durante la invencibilidad el sprite alterna entre visible y transparente con este periodo
+const INVINCIBILITY_BLINK_INTERVAL: f32 = 0.1;
+const INVINCIBILITY_BLINK_ALPHA: f32 = 0.25;
+const HIT_FLASH_COLOR: Color = Color::srgb(1.0, 0.3, 0.3);
+// Fotogramas de la hoja de corazones: lleno, medio y vacío
+const HEART_FULL_FRAME: usize = 0;
+const HEART_HALF_FRAME: usize = 10;
+const HEART_EMPTY_FRAME: usize = 20;
+const HEART_ANIMATION_FPS: u8 = 24;
+// Separación horizontal entre players al aparecer en co-op
+pub const PLAYER_SPAWN_SPACING: f32 = 32.0;
 
 // Sistema principal de físicas del personaje
 pub fn player_input_system(
-    keyboard: Res<ButtonInput<KeyCode>>,
+    time: Res<Time>,
+    player_actions: Res<PlayerActions>,
+    tuning: Res<MovementTuning>,
+    mut query: Query<(
+        Entity,
+        &PlayerId,
+        &mut Velocity,
+        &KinematicCharacterControllerOutput,
+        &mut DoubleJump,
+        &mut JumpTiming,
+    )>,
+    // Estadísticas del gato y power-up de velocidad, si los tiene
+    modifiers_query: Query<(&CharacterStats, Option<&SpeedBoost>)>,
+    wall_slide_query: Query<&WallSlide>,
+    // Sin gravedad (durante el dash o colgado de un borde) la velocidad la controlan
+    // dash_system y ledge_hang_system; con knockback, el empujón. Los caídos no se controlan
+    busy_query: Query<(Has<IgnoreGravity>, Has<Downed>, Has<Knockback>)>,
+) {
+    for (entity, player, mut velocity, output, mut double_jump, mut jump_timing) in &mut query {
+        let actions = player_actions.get(player);
+        if busy_query
+            .get(entity)
+            .is_ok_and(|(no_gravity, downed, knocked_back)| no_gravity || downed || knocked_back)
+        {
+            continue;
+        }
+
+        let wall_slide = wall_slide_query.get(entity).ok();
+
+        let Ok((stats, speed_boost)) = modifiers_query.get(entity) else {
+            continue;
+        };
+        let multiplier = speed_boost.map_or(1.0, |boost| boost.multiplier);
+
+        let on_ground = is_on_ground(output, &velocity);
+
+        // Movimiento lateral: acelera hacia la velocidad objetivo, con curvas distintas en suelo y aire
+        let mut direction = 0.0;
+        if actions.pressed(Action::MoveLeft) {
+            direction -= 1.0;
+        }
+        if actions.pressed(Action::MoveRight) {
+            direction += 1.0;
+        }
+        let target = direction * stats.run_speed * multiplier;
+        let rate = match (on_ground, direction != 0.0) {
+            (true, true) => tuning.ground_acceleration,
+            (true, false) => tuning.ground_deceleration,
+            (false, true) => tuning.air_acceleration,
+            (false, false) => tuning.air_deceleration,
+        };
+        let max_delta = rate * multiplier * time.delta_secs();
+        velocity.velocity.x += (target - velocity.velocity.x).clamp(-max_delta, max_delta);
+
+        // Deslizando por una pared la caída está limitada
+        if wall_slide.is_some() {
+            velocity.velocity.y = velocity.velocity.y.max(-tuning.wall_slide_speed);
+        }
+
+        // Si el coyote time se agota sin haber saltado, se pierde el salto desde el suelo
+        let coyote_expired =
+            jump_timing.tick(time.delta(), on_ground, actions.just_pressed(Action::Jump));
+        if coyote_expired && double_jump.jumps_remaining == double_jump.max_jumps {
+            double_jump.jumps_remaining -= 1;
+        }
+
+        // Soltar salto mientras se sube corta el salto (saltos cortos)
+        if actions.just_released(Action::Jump) && velocity.velocity.y > 0.0 {
+            velocity.velocity.y *= tuning.jump_cut_multiplier;
+        }
+
+        if !jump_timing.jump_buffered() {
+            continue;
+        }
+
+        if jump_timing.in_coyote_time() {
+            // Salto desde el suelo (o dentro del coyote time): gasta el primer salto
+            double_jump.jumps_remaining = double_jump.max_jumps.saturating_sub(1);
+        } else if let Some(wall_slide) = wall_slide {
+            // Salto de pared: empuja en dirección contraria y recupera un salto en el aire
+            velocity.velocity.x = -wall_slide.direction * tuning.wall_jump_push * multiplier;
+            double_jump.jumps_remaining =
+                (double_jump.jumps_remaining + 1).min(double_jump.max_jumps.saturating_sub(1));
+        } else if double_jump.jumps_remaining > 0 {
+            double_jump.jumps_remaining -= 1;
+        } else {
+            continue;
+        }
+
+        velocity.velocity.y = stats.jump_velocity * multiplier;
+        // Un salto del buffer cuya tecla ya se soltó sale directamente corto
+        if !actions.pressed(Action::Jump) {
+            velocity.velocity.y *= tuning.jump_cut_multiplier;
+        }
+        jump_timing.consume();
+    }
+}
+
+// El output del controlador llega con un frame de retraso: justo después de saltar
+// todavía marca grounded, así que solo cuenta como suelo si no se está subiendo
+fn is_on_ground(output: &KinematicCharacterControllerOutput, velocity: &Velocity) -> bool {
+    output.grounded && velocity.velocity.y <= 0.0
+}
+
+// Detecta si el player se desliza por una pared: en el aire, cayendo, chocando contra
+// una superficie casi vertical y manteniendo la dirección hacia ella
+pub fn wall_slide_detection_system(
+    mut commands: Commands,
+    player_actions: Res<PlayerActions>,
+    query: Query<(
+        Entity,
+        &PlayerId,
+        &KinematicCharacterControllerOutput,
+        &Velocity,
+        Has<WallSlide>,
+    )>,
+) {
+    for (entity, player, output, velocity, sliding_now) in &query {
+        let actions = player_actions.get(player);
+        let wall_direction = output
+            .collisions
+            .iter()
+            .filter_map(|collision| collision.hit.details)
+            // La normal de la pared apunta hacia el player
+            .find(|details| details.normal2.y.abs() < WALL_NORMAL_MAX_Y)
+            .map(|details| -details.normal2.x.signum());
+
+        let sliding = wall_direction.filter(|&direction| {
+            let holding_toward = if direction > 0.0 {
+                actions.pressed(Action::MoveRight)
+            } else {
+                actions.pressed(Action::MoveLeft)
+            };
+            !output.grounded && velocity.velocity.y <= 0.0 && holding_toward
+        });
+
+        match (sliding, sliding_now) {
+            (Some(direction), _) => {
+                commands.entity(entity).insert(WallSlide { direction });
+            }
+            (None, true) => {
+                commands.entity(entity).remove::<WallSlide>();
+            }
+            (None, false) => {}
+        }
+    }
+}
+
+// Agarra el borde de un tile sólido al caer junto a su esquina superior manteniendo la
+// dirección hacia él. Un rayo a media altura busca la pared y otro, que baja desde encima
+// de la cabeza por delante del player, la parte de arriba del tile; si ese segundo rayo
+// empieza dentro de un tile no hay borde, sino más pared
+pub fn ledge_grab_system(
+    mut commands: Commands,
+    rapier_context: ReadRapierContext,
+    player_actions: Res<PlayerActions>,
+    mut query: Query<(
+        Entity,
+        &PlayerId,
+        &mut Transform,
+        &mut Velocity,
+        &Collider,
+        &KinematicCharacterControllerOutput,
+    )>,
+    // Colgado o en pleno dash ya no hay gravedad; tampoco se agarra con knockback o caído
+    busy_query: Query<(Has<IgnoreGravity>, Has<Downed>, Has<Knockback>)>,
+) {
+    let Ok(context) = rapier_context.single() else {
+        return;
+    };
+    // Solo los tiles sólidos: las plataformas de un sentido no tienen borde que agarrar
+    let filter = QueryFilter::only_fixed()
+        .exclude_sensors()
+        .groups(CollisionGroups::new(
+            Group::ALL,
+            Group::ALL & !ONE_WAY_PLATFORM_GROUP,
+        ));
+
+    for (entity, player, mut transform, mut velocity, collider, output) in &mut query {
+        if output.grounded
+            || velocity.velocity.y > 0.0
+            || busy_query
+                .get(entity)
+                .is_ok_and(|(no_gravity, downed, knocked_back)| {
+                    no_gravity || downed || knocked_back
+                })
+        {
+            continue;
+        }
+        let actions = player_actions.get(player);
+        let direction = match (
+            actions.pressed(Action::MoveLeft),
+            actions.pressed(Action::MoveRight),
+        ) {
+            (true, false) => -1.0,
+            (false, true) => 1.0,
+            _ => continue,
+        };
+
+        let center = transform.translation.xy();
+        let (min, max) = collider_bounds(&GlobalTransform::from(*transform), collider);
+        let half_width = (max.x - min.x) / 2.0;
+        let half_height = (max.y - min.y) / 2.0;
+
+        let Some((_, wall_toi)) = context.cast_ray(
+            center,
+            Vec2::new(direction, 0.0),
+            half_width + LEDGE_REACH,
+            true,
+            filter,
+        ) else {
+            continue;
+        };
+
+        let probe = Vec2::new(
+            center.x + direction * (half_width + LEDGE_REACH),
+            max.y + LEDGE_PROBE_HEIGHT,
+        );
+        let Some((_, ledge_toi)) = context.cast_ray(
+            probe,
+            Vec2::NEG_Y,
+            LEDGE_PROBE_HEIGHT + half_height,
+            true,
+            filter,
+        ) else {
+            continue;
+        };
+        if ledge_toi <= 0.0 {
+            continue;
+        }
+
+        let ledge_top = probe.y - ledge_toi;
+        let wall_x = center.x + direction * wall_toi;
+        let top = Vec2::new(
+            wall_x + direction * half_width,
+            ledge_top + half_height + 1.0,
+        );
+
+        // Si encima del borde no cabe el player (techo bajo), no se agarra
+        let mut blocked = false;
+        context.intersections_with_shape(
+            top,
+            0.0,
+            &Collider::cuboid(half_width, half_height),
+            filter,
+            |_| {
+                blocked = true;
+                false
+            },
+        );
+        if blocked {
+            continue;
+        }
+
+        let hang = Vec2::new(
+            wall_x - direction * half_width,
+            ledge_top - LEDGE_HANG_OFFSET,
+        );
+        transform.translation.x = hang.x;
+        transform.translation.y = hang.y;
+        velocity.velocity = Vec2::ZERO;
+        commands
+            .entity(entity)
+            .insert((
+                LedgeHang {
+                    direction,
+                    hang,
+                    top,
+                    climb: None,
+                },
+                IgnoreGravity,
+            ))
+            .remove::<WallSlide>();
+    }
+}
+
+// Colgado de un borde: salto (o arriba, que comparte tecla) trepa; abajo o la dirección
+// contraria a la pared lo suelta. Un golpe, caer o empezar un dash también lo sueltan
+pub fn ledge_hang_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    player_actions: Res<PlayerActions>,
+    mut query: Query<(
+        Entity,
+        &PlayerId,
+        &mut LedgeHang,
+        &mut Transform,
+        &mut Velocity,
+    )>,
+    busy_query: Query<(Has<Dashing>, Has<Downed>, Has<Knockback>)>,
+) {
+    for (entity, player, mut ledge, mut transform, mut velocity) in &mut query {
+        let (dashing, downed, knocked_back) = busy_query.get(entity).unwrap_or_default();
+        let actions = player_actions.get(player);
+
+        if dashing {
+            // El dash ya controla la gravedad
+            commands.entity(entity).remove::<LedgeHang>();
+            continue;
+        }
+        let away = if ledge.direction > 0.0 {
+            Action::MoveLeft
+        } else {
+            Action::MoveRight
+        };
+        let let_go = actions.just_pressed(Action::MoveDown) || actions.just_pressed(away);
+        if downed || knocked_back || (ledge.climb.is_none() && let_go) {
+            commands
+                .entity(entity)
+                .remove::<(LedgeHang, IgnoreGravity)>();
+            continue;
+        }
+
+        velocity.velocity = Vec2::ZERO;
+        let LedgeHang {
+            hang, top, climb, ..
+        } = &mut *ledge;
+        let Some(climb) = climb else {
+            if actions.just_pressed(Action::Jump) {
+                *climb = Some(Timer::from_seconds(LEDGE_CLIMB_TIME, TimerMode::Once));
+            }
+            continue;
+        };
+
+        climb.tick(time.delta());
+        let t = climb.fraction();
+        // Primero sube junto a la pared y después avanza sobre el borde
+        let position = if t < LEDGE_CLIMB_RISE {
+            Vec2::new(hang.x, hang.y.lerp(top.y, t / LEDGE_CLIMB_RISE))
+        } else {
+            Vec2::new(
+                hang.x
+                    .lerp(top.x, (t - LEDGE_CLIMB_RISE) / (1.0 - LEDGE_CLIMB_RISE)),
+                top.y,
+            )
+        };
+        transform.translation.x = position.x;
+        transform.translation.y = position.y;
+
+        if climb.finished() {
+            commands
+                .entity(entity)
+                .remove::<(LedgeHang, IgnoreGravity)>();
+        }
+    }
+}
+
+// Dash: ráfaga de distancia fija hacia la dirección pulsada (o hacia donde mira el player),
+// sin gravedad y con una invencibilidad breve. Se aplica a través de Velocity, así que lo
+// mueve kinematic_character_movement_system como cualquier otro movimiento
+pub fn dash_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    player_actions: Res<PlayerActions>,
+    tuning: Res<MovementTuning>,
     mut query: Query<
         (
+            Entity,
+            &PlayerId,
+            &mut Dash,
             &mut Velocity,
             &KinematicCharacterControllerOutput,
-            &mut DoubleJump,
         ),
-        With<PlayerCharacter>,
+        Without<Downed>,
     >,
+    mut dashing_query: Query<(&mut Dashing, &CharacterStats)>,
+    invincibility_query: Query<&Invincibility>,
 ) {
-    for (mut velocity, output, mut double_jump) in &mut query {
-        // Movimiento lateral (sin acumulación, directo)
-        let mut horizontal = 0.0;
-        if keyboard.pressed(KeyCode::ArrowLeft) || keyboard.pressed(KeyCode::KeyA) {
-            horizontal -= HORIZONTAL_FORCE;
+    for (entity, player, mut dash, mut velocity, output) in &mut query {
+        let actions = player_actions.get(player);
+        dash.cooldown.tick(time.delta());
+
+        dash.facing = match (
+            actions.pressed(Action::MoveLeft),
+            actions.pressed(Action::MoveRight),
+        ) {
+            (true, false) => -1.0,
+            (false, true) => 1.0,
+            _ => dash.facing,
+        };
+
+        let on_ground = is_on_ground(output, &velocity);
+        if on_ground {
+            dash.air_dash_available = true;
         }
-        if keyboard.pressed(KeyCode::ArrowRight) || keyboard.pressed(KeyCode::KeyD) {
-            horizontal += HORIZONTAL_FORCE;
+
+        if let Ok((mut dashing, stats)) = dashing_query.get_mut(entity) {
+            dashing.timer.tick(time.delta());
+            if dashing.timer.finished() {
+                // Al terminar se sale a velocidad de carrera, no a la del dash
+                velocity.velocity = Vec2::new(dashing.velocity.x.signum() * stats.run_speed, 0.0);
+                commands.entity(entity).remove::<(Dashing, IgnoreGravity)>();
+            } else {
+                velocity.velocity = dashing.velocity;
+            }
+            continue;
         }
 
-        velocity.velocity.x = horizontal;
+        if !actions.just_pressed(Action::Dash) || !dash.cooldown.finished() {
+            continue;
+        }
 
-        // Salto (solo una vez al presionar)
-        if keyboard.just_pressed(KeyCode::ArrowUp) || keyboard.just_pressed(KeyCode::KeyW) {
-            if output.grounded || double_jump.jumps_remaining > 0 {
-                velocity.velocity.y = JUMP_FORCE;
-                double_jump.jumps_remaining -= 1;
-            }
+        // En el aire solo se puede hacer un dash hasta volver a tocar el suelo
+        if !on_ground && !dash.air_dash_available {
+            continue;
+        }
+        if !on_ground {
+            dash.air_dash_available = false;
         }
+
+        // El desplazamiento por frame es velocity * SMOOTHING_FACTOR * dt
+        let speed = tuning.dash_distance / (SMOOTHING_FACTOR * tuning.dash_duration);
+        let dash_velocity = Vec2::new(dash.facing * speed, 0.0);
+        velocity.velocity = dash_velocity;
+        dash.cooldown.reset();
+
+        commands.entity(entity).insert((
+            Dashing {
+                velocity: dash_velocity,
+                timer: Timer::from_seconds(tuning.dash_duration, TimerMode::Once),
+            },
+            IgnoreGravity,
+        ));
+        // No acorta una invencibilidad más larga (p. ej. la de reaparecer)
+        if invincibility_query
+            .get(entity)
+            .ok()
+            .is_none_or(|inv| inv.timer.remaining_secs() < tuning.dash_invincibility)
+        {
+            commands
+                .entity(entity)
+                .insert(Invincibility::new(tuning.dash_invincibility));
+        }
+    }
+}
+
+// Elige la animación del player a partir de su estado de juego (vida, pared, suelo y
+// velocidad); los clips de daño y lanzar los disparan los sistemas que los provocan
+pub fn update_player_animation(
+    mut query: Query<
+        (
+            Entity,
+            &mut CharacterAnimator,
+            &Health,
+            &Velocity,
+            &KinematicCharacterControllerOutput,
+        ),
+        With<PlayerCharacter>,
+    >,
+    wall_slide_query: Query<(), With<WallSlide>>,
+    ledge_query: Query<&LedgeHang>,
+) {
+    for (entity, mut animator, health, velocity, output) in &mut query {
+        let state = if health.current == 0 {
+            AnimationState::Death
+        } else if let Ok(ledge) = ledge_query.get(entity) {
+            if ledge.climb.is_some() {
+                AnimationState::Jump
+            } else {
+                AnimationState::LedgeHang
+            }
+        } else if wall_slide_query.contains(entity) {
+            AnimationState::WallSlide
+        } else if !is_on_ground(output, velocity) {
+            if velocity.velocity.y > 0.0 {
+                AnimationState::Jump
+            } else {
+                AnimationState::Fall
+            }
+        } else if velocity.velocity.x.abs() > WALK_ANIMATION_MIN_SPEED {
+            AnimationState::Walk
+        } else {
+            AnimationState::Idle
+        };
+        animator.set_state(state);
     }
 }
 
 pub fn reset_jumps(
-    mut query: Query<(&KinematicCharacterControllerOutput, &mut DoubleJump), With<PlayerCharacter>>,
+    mut query: Query<
+        (
+            &KinematicCharacterControllerOutput,
+            &Velocity,
+            &mut DoubleJump,
+        ),
+        With<PlayerCharacter>,
+    >,
 ) {
-    for (output, mut double_jump) in &mut query {
-        if output.grounded {
+    for (output, velocity, mut double_jump) in &mut query {
+        if is_on_ground(output, velocity) {
             double_jump.jumps_remaining = double_jump.max_jumps;
         }
     }
 }
 
-pub fn update_player_life(
+// Rehace los corazones de cada player a partir de su Health cuando cambia la vida actual o
+// la máxima: añade o quita contenedores y anima cada corazón hacia su fotograma
+pub fn update_player_hearts(
     mut commands: Commands,
-    player_query: Query<&Health, (With<PlayerCharacter>, Changed<Health>)>,
-    heart_query: Query<(Entity, &PlayerHearts)>,
+    player_assets: Res<PlayerAssets>,
+    player_query: Query<(&PlayerId, &Health), Changed<Health>>,
+    mut heart_query: Query<(Entity, &mut PlayerHearts)>,
 ) {
-    // Solo se ejecuta si la vida del jugador ha cambiado
-    let Ok(player_health) = player_query.single() else {
-        return;
-    };
+    for (player, health) in player_query.iter() {
+        let hearts = health.hearts() as usize;
+        let mut existing = vec![false; hearts];
 
-    let active_heart_index = player_health.current as usize;
+        for (entity, mut heart) in heart_query.iter_mut() {
+            if heart.player != *player {
+                continue;
+            }
+            if heart.idx >= hearts {
+                commands.entity(entity).despawn();
+                continue;
+            }
+            existing[heart.idx] = true;
+            heart.target_frame = heart_frame(health, heart.idx);
+        }
 
-    for (entity, heart_data) in heart_query.iter() {
-        if heart_data.idx == active_heart_index {
-            commands
-                .entity(entity)
-                .insert(AnimationIndices::new(0, 21, ANIMATION_FPS));
-        } else {
-            commands.entity(entity).remove::<AnimationIndices>();
+        // Los contenedores nuevos aparecen vacíos y se llenan con la animación
+        for idx in (0..hearts).filter(|idx| !existing[*idx]) {
+            let x_position = 14.0 + 45.0 * idx as f32;
+            let mut node = Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(7.0),
+                ..default()
+            };
+            if player.0 == 0 {
+                node.left = Val::Px(x_position);
+            } else {
+                node.right = Val::Px(x_position);
+            }
+
+            commands.spawn((
+                PlayerHearts::new(idx, *player, heart_frame(health, idx), HEART_ANIMATION_FPS),
+                ImageNode {
+                    image: player_assets.hearts.clone(),
+                    texture_atlas: Some(TextureAtlas {
+                        layout: player_assets.hearts_layout.clone(),
+                        index: HEART_EMPTY_FRAME,
+                    }),
+                    ..default()
+                },
+                node,
+                Transform::from_scale(Vec3::splat(0.25)),
+            ));
+        }
+    }
+}
+
+// Fotograma del corazón `idx` según los medios corazones que le quedan
+fn heart_frame(health: &Health, idx: usize) -> usize {
+    let heart_start = idx as u32 * HALF_HEARTS_PER_HEART;
+    match health.current.saturating_sub(heart_start) {
+        0 => HEART_EMPTY_FRAME,
+        1 => HEART_HALF_FRAME,
+        _ => HEART_FULL_FRAME,
+    }
+}
+
+// El contador de vidas va justo a la derecha de los corazones del jugador 1
+pub fn update_lives_hud_position(
+    player_query: Query<(&PlayerId, &Health), Changed<Health>>,
+    mut lives_query: Query<&mut Node, With<LivesHud>>,
+) {
+    for (player, health) in player_query.iter() {
+        if player.0 != 0 {
+            continue;
+        }
+        for mut node in lives_query.iter_mut() {
+            node.left = Val::Px(lives_hud_left(health));
         }
     }
 }
 
+pub fn lives_hud_left(health: &Health) -> f32 {
+    14.0 + 45.0 * health.hearts() as f32 + 10.0
+}
+
+// Un player sin corazones queda caído; solo cuando caen todos se pierde una vida
 pub fn check_player_death(
-    player_query: Query<&Health, (With<PlayerCharacter>, Changed<Health>)>,
+    changed_query: Query<(Entity, &Health), Changed<Health>>,
+    player_query: Query<&Health, With<PlayerCharacter>>,
+    downed_query: Query<(), With<Downed>>,
     mut commands: Commands,
-    game_over_timer: Option<Res<GameOverTimer>>,
+    mut lives: ResMut<Lives>,
+    respawn_timer: Option<Res<RespawnTimer>>,
 ) {
-    let Ok(player_health) = player_query.single() else {
+    if changed_query.is_empty() {
         return;
-    };
-    if player_health.current == 0 {
-        // Solo inserta el temporizador si no existe ya
-        if game_over_timer.is_none() {
-            println!("La vida del jugador llegó a cero. Esperando 2 segundos...");
-            commands.insert_resource(GameOverTimer(Timer::from_seconds(2.0, TimerMode::Once)));
+    }
+
+    for (entity, health) in changed_query.iter() {
+        if health.current == 0 && !downed_query.contains(entity) {
+            commands.entity(entity).insert(Downed::new(REVIVE_TIME));
         }
     }
+
+    let all_down = player_query.iter().all(|health| health.current == 0);
+    // Solo inserta el temporizador si no existe ya
+    if all_down && respawn_timer.is_none() {
+        lives.current = lives.current.saturating_sub(1);
+        commands.insert_resource(RespawnTimer(Timer::from_seconds(
+            RESPAWN_DELAY,
+            TimerMode::Once,
+        )));
+    }
 }
 
-pub fn handle_gameover_timer(
+// Al terminar el temporizador, los players reaparecen en el último checkpoint,
+// o se pasa a GameOver si no quedan vidas
+pub fn handle_respawn_timer(
     mut commands: Commands,
     mut next_state: ResMut<NextState<GameState>>,
-    game_over_timer: Option<ResMut<GameOverTimer>>,
+    lives: Res<Lives>,
+    respawn_timer: Option<ResMut<RespawnTimer>>,
+    respawn_point: Option<Res<RespawnPoint>>,
+    // Solo los players tienen Health
+    mut player_query: Query<(
+        Entity,
+        &PlayerId,
+        &mut Transform,
+        &mut Health,
+        &mut WoolAmmo,
+        &mut Velocity,
+    )>,
     time: Res<Time>,
 ) {
-    if let Some(mut timer) = game_over_timer {
-        timer.0.tick(time.delta());
+    let (Some(mut timer), Some(respawn_point)) = (respawn_timer, respawn_point) else {
+        return;
+    };
 
-        if timer.0.finished() {
-            println!("Temporizador terminado. Cambiando a GameState::GameOver.");
-            next_state.set(GameState::GameOver);
-            commands.remove_resource::<GameOverTimer>();
-            // El temporizador se eliminará automáticamente cuando cambie el estado
-        }
+    timer.0.tick(time.delta());
+    if !timer.0.finished() {
+        return;
     }
-}
+    commands.remove_resource::<RespawnTimer>();
 
-pub fn animate_hearts(time: Res<Time>, mut query: Query<(&mut AnimationIndices, &mut ImageNode)>) {
-    for (mut config, mut sprite) in &mut query {
-        config.frame_timer.tick(time.delta());
+    if lives.current == 0 {
+        next_state.set(GameState::GameOver);
+        return;
+    }
 
-        if config.frame_timer.just_finished() {
-            if let Some(atlas) = &mut sprite.texture_atlas {
-                if atlas.index != config.last - 1 {
-                    atlas.index += 1;
-                    config.frame_timer = AnimationIndices::timer_from_fps(config.fps);
-                }
-            }
-        }
+    for (entity, player, mut transform, mut health, mut ammo, mut velocity) in
+        player_query.iter_mut()
+    {
+        transform.translation = respawn_point.0;
+        transform.translation.x += player.0 as f32 * PLAYER_SPAWN_SPACING;
+        health.current = health.max;
+        ammo.current = ammo.max;
+        velocity.velocity = Vec2::ZERO;
+        commands
+            .entity(entity)
+            .remove::<Downed>()
+            .insert(Invincibility::new(RESPAWN_INVINCIBILITY));
     }
 }
 
-// Sistema para manejar las animaciones
-pub fn execute_animations(time: Res<Time>, mut query: Query<(&mut AnimationIndices, &mut Sprite)>) {
-    for (mut config, mut sprite) in &mut query {
-        config.frame_timer.tick(time.delta());
+// Un player caído se reanima si un compañero en pie se queda cerca el tiempo suficiente.
+// Mientras está caído no se mueve en horizontal
+pub fn revive_downed_players(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut downed_query: Query<(Entity, &Transform, &mut Downed, &mut Health, &mut Velocity)>,
+    // Solo los players tienen Health
+    helper_query: Query<(&Transform, &Health), Without<Downed>>,
+) {
+    for (entity, transform, mut downed, mut health, mut velocity) in downed_query.iter_mut() {
+        velocity.velocity.x = 0.0;
 
-        if config.frame_timer.just_finished() {
-            if let Some(atlas) = &mut sprite.texture_atlas {
-                if atlas.index == config.last {
-                    atlas.index = config.first;
-                } else {
-                    atlas.index += 1;
-                    config.frame_timer = AnimationIndices::timer_from_fps(config.fps);
-                }
-            }
+        let helper_near = helper_query
+            .iter()
+            .any(|(helper_transform, helper_health)| {
+                helper_health.current > 0
+                    && helper_transform
+                        .translation
+                        .xy()
+                        .distance(transform.translation.xy())
+                        <= REVIVE_RADIUS
+            });
+
+        if !helper_near {
+            downed.revive.reset();
+            continue;
+        }
+
+        downed.revive.tick(time.delta());
+        if downed.revive.finished() {
+            health.current = health.max.div_ceil(2);
+            commands
+                .entity(entity)
+                .remove::<Downed>()
+                .insert(Invincibility::new(REVIVE_INVINCIBILITY));
         }
     }
 }
 
-pub fn character_input_handling(
-    keyboard: Res<ButtonInput<KeyCode>>,
-    mut visibility_queries: ParamSet<(
-        Query<&mut Visibility, With<CharacterLeftSprite>>,
-        Query<&mut Visibility, With<CharacterRightSprite>>,
-        Query<&mut Visibility, With<CharacterIdleSprite>>,
-    )>,
-    player_parent_query: Query<&Children, With<PlayerCharacter>>,
+// Muestra sobre el player caído si necesita ayuda o cuánto le falta para levantarse
+pub fn update_revive_text(
+    mut commands: Commands,
+    downed_query: Query<(Entity, &Downed, Option<&Children>), With<PlayerCharacter>>,
+    mut text_query: Query<(Entity, &ChildOf, &mut Text2d), With<ReviveText>>,
 ) {
-    let left = keyboard.pressed(KeyCode::ArrowLeft) || keyboard.pressed(KeyCode::KeyA);
-    let right = keyboard.pressed(KeyCode::ArrowRight) || keyboard.pressed(KeyCode::KeyD);
+    for (entity, parent, mut text) in text_query.iter_mut() {
+        match downed_query.get(parent.parent()) {
+            Ok((_, downed, _)) if downed.revive.elapsed_secs() > 0.0 => {
+                text.0 = format!("{:.0}%", downed.revive.fraction() * 100.0);
+            }
+            Ok(_) => text.0 = "Help!".to_string(),
+            Err(_) => commands.entity(entity).despawn(),
+        }
+    }
+
+    for (entity, _, children) in downed_query.iter() {
+        let has_text = children
+            .is_some_and(|children| children.iter().any(|child| text_query.contains(child)));
+        if !has_text {
+            commands.entity(entity).with_child((
+                ReviveText,
+                Text2d::new("Help!"),
+                TextFont {
+                    font_size: 14.0,
+                    ..default()
+                },
+                Transform::from_xyz(0.0, 36.0, 1.0),
+            ));
+        }
+    }
+}
 
-    // Obtenemos los hijos del PlayerCharacter.
-    let Ok(player_children) = player_parent_query.single() else {
+pub fn update_lives_hud(lives: Res<Lives>, mut text_query: Query<&mut Text, With<LivesHudText>>) {
+    if !lives.is_changed() {
         return;
-    };
-    // La lógica de visibilidad ahora se aplica a los hijos del jugador.
-    for child in player_children.iter() {
-        match (left, right) {
-            (true, false) => {
-                if let Ok(mut v) = visibility_queries.p0().get_mut(child) {
-                    *v = Visibility::Visible;
-                } else if let Ok(mut v) = visibility_queries.p1().get_mut(child) {
-                    *v = Visibility::Hidden;
-                } else if let Ok(mut v) = visibility_queries.p2().get_mut(child) {
-                    *v = Visibility::Hidden;
-                }
-            }
-            (false, true) => {
-                if let Ok(mut v) = visibility_queries.p0().get_mut(child) {
-                    *v = Visibility::Hidden;
-                } else if let Ok(mut v) = visibility_queries.p1().get_mut(child) {
-                    *v = Visibility::Visible;
-                } else if let Ok(mut v) = visibility_queries.p2().get_mut(child) {
-                    *v = Visibility::Hidden;
-                }
-            }
-            _ => {
-                if let Ok(mut v) = visibility_queries.p0().get_mut(child) {
-                    *v = Visibility::Hidden;
-                } else if let Ok(mut v) = visibility_queries.p1().get_mut(child) {
-                    *v = Visibility::Hidden;
-                } else if let Ok(mut v) = visibility_queries.p2().get_mut(child) {
-                    *v = Visibility::Visible;
-                }
+    }
+
+    for mut text in text_query.iter_mut() {
+        text.0 = format!("x {}", lives.current);
+    }
+}
+
+// Avanza o retrocede cada corazón un fotograma por tick hasta llegar a su objetivo
+pub fn animate_hearts(time: Res<Time>, mut query: Query<(&mut PlayerHearts, &mut ImageNode)>) {
+    for (mut heart, mut image) in &mut query {
+        let Some(atlas) = &mut image.texture_atlas else {
+            continue;
+        };
+        if atlas.index == heart.target_frame {
+            continue;
+        }
+
+        heart.frame_timer.tick(time.delta());
+        if heart.frame_timer.just_finished() {
+            if atlas.index < heart.target_frame {
+                atlas.index += 1;
+            } else {
+                atlas.index -= 1;
             }
         }
     }
@@ -232,10 +859,294 @@ pub fn invincibility_system(
     mut query: Query<(Entity, &mut Invincibility), With<PlayerCharacter>>,
 ) {
     for (entity, mut invincibility) in query.iter_mut() {
-        println!("Hell yea i'm invincible!");
         invincibility.timer.tick(time.delta());
         if invincibility.timer.finished() {
             commands.entity(entity).remove::<Invincibility>();
         }
     }
 }
+
+const SPEED_BOOST_TINT: Color = Color::srgb(1.0, 0.75, 0.35);
+
+// Descuenta el boost de velocidad
+pub fn speed_boost_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut player_query: Query<(Entity, &mut SpeedBoost), With<PlayerCharacter>>,
+) {
+    for (entity, mut speed_boost) in player_query.iter_mut() {
+        speed_boost.timer.tick(time.delta());
+        if speed_boost.timer.finished() {
+            commands.entity(entity).remove::<SpeedBoost>();
+        }
+    }
+}
+
+// Color del sprite del player: destello al recibir daño, parpadeo mientras es invencible
+// y tinte del boost de velocidad
+pub fn player_sprite_feedback_system(
+    player_query: Query<(Entity, &Children), With<PlayerCharacter>>,
+    state_query: Query<(Has<HitFlash>, Has<SpeedBoost>, Option<&Invincibility>)>,
+    mut sprite_query: Query<&mut Sprite, With<CharacterSprite>>,
+) {
+    for (entity, children) in player_query.iter() {
+        let Ok((flashing, boosted, invincibility)) = state_query.get(entity) else {
+            continue;
+        };
+
+        let mut color = if flashing {
+            HIT_FLASH_COLOR
+        } else if boosted {
+            SPEED_BOOST_TINT
+        } else {
+            Color::WHITE
+        };
+        if let Some(invincibility) = invincibility {
+            let blink = (invincibility.timer.elapsed_secs() / INVINCIBILITY_BLINK_INTERVAL) as u32;
+            if blink % 2 == 1 {
+                color = color.with_alpha(INVINCIBILITY_BLINK_ALPHA);
+            }
+        }
+
+        for child in children.iter() {
+            if let Ok(mut sprite) = sprite_query.get_mut(child) {
+                sprite.color = color;
+            }
+        }
+    }
+}
+
+// Reacción a los golpes, igual venga de donde venga el daño: empujón lejos de la fuente,
+// destello, animación de daño y un hit-stop breve
+pub fn apply_player_hits(
+    mut commands: Commands,
+    mut hit_events: EventReader<PlayerHit>,
+    mut player_query: Query<(&mut Velocity, &mut CharacterAnimator), With<PlayerCharacter>>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+) {
+    for hit in hit_events.read() {
+        let Ok((mut velocity, mut animator)) = player_query.get_mut(hit.player) else {
+            continue;
+        };
+
+        let side = if hit.direction.x < 0.0 { -1.0 } else { 1.0 };
+        velocity.velocity = Vec2::new(side * KNOCKBACK_VELOCITY.x, KNOCKBACK_VELOCITY.y);
+        animator.play_once(AnimationState::Hurt);
+
+        commands.entity(hit.player).insert((
+            Knockback {
+                timer: Timer::from_seconds(KNOCKBACK_TIME, TimerMode::Once),
+            },
+            HitFlash {
+                timer: Timer::from_seconds(HIT_FLASH_TIME, TimerMode::Once),
+            },
+        ));
+
+        virtual_time.pause();
+        commands.insert_resource(HitStop {
+            timer: Timer::from_seconds(HIT_STOP_TIME, TimerMode::Once),
+        });
+    }
+}
+
+// Reanuda el tiempo del juego al terminar el hit-stop
+pub fn hit_stop_system(
+    mut commands: Commands,
+    real_time: Res<Time<Real>>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+    hit_stop: Option<ResMut<HitStop>>,
+) {
+    let Some(mut hit_stop) = hit_stop else {
+        return;
+    };
+
+    hit_stop.timer.tick(real_time.delta());
+    if hit_stop.timer.finished() {
+        virtual_time.unpause();
+        commands.remove_resource::<HitStop>();
+    }
+}
+
+// Descuenta el empujón y el destello de los golpes
+pub fn hit_reaction_timers_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut knockback_query: Query<(Entity, &mut Knockback)>,
+    mut flash_query: Query<(Entity, &mut HitFlash)>,
+) {
+    for (entity, mut knockback) in knockback_query.iter_mut() {
+        knockback.timer.tick(time.delta());
+        if knockback.timer.finished() {
+            commands.entity(entity).remove::<Knockback>();
+        }
+    }
+    for (entity, mut flash) in flash_query.iter_mut() {
+        flash.timer.tick(time.delta());
+        if flash.timer.finished() {
+            commands.entity(entity).remove::<HitFlash>();
+        }
+    }
+}
+
+pub const DASH_READY_COLOR: Color = Color::srgb(0.4, 0.85, 1.0);
+const DASH_COOLDOWN_COLOR: Color = Color::srgb(0.45, 0.45, 0.5);
+
+// Rellena la barra del dash según el enfriamiento; se ve gris si aún no está disponible
+pub fn update_dash_hud(
+    player_query: Query<(&PlayerId, &Dash), With<PlayerCharacter>>,
+    mut fill_query: Query<(&DashHudFill, &mut Node, &mut BackgroundColor)>,
+) {
+    for (fill, mut node, mut color) in fill_query.iter_mut() {
+        let Some((_, dash)) = player_query.iter().find(|(player, _)| **player == fill.0) else {
+            continue;
+        };
+        let ready = dash.cooldown.finished() && dash.air_dash_available;
+        node.width = Val::Percent(dash.cooldown.fraction() * 100.0);
+        color.0 = if ready {
+            DASH_READY_COLOR
+        } else {
+            DASH_COOLDOWN_COLOR
+        };
+    }
+}
+
+// Muestra el icono del boost de cada player con el tiempo restante mientras esté activo
+pub fn update_speed_boost_hud(
+    mut commands: Commands,
+    player_assets: Res<PlayerAssets>,
+    player_query: Query<(&PlayerId, Option<&SpeedBoost>), With<PlayerCharacter>>,
+    hud_query: Query<(Entity, &SpeedBoostHud)>,
+    mut text_query: Query<(&mut Text, &SpeedBoostHudText)>,
+) {
+    for (player, speed_boost) in player_query.iter() {
+        let hud = hud_query.iter().find(|(_, hud)| hud.0 == *player);
+
+        let Some(speed_boost) = speed_boost else {
+            if let Some((entity, _)) = hud {
+                commands.entity(entity).despawn();
+            }
+            continue;
+        };
+
+        let remaining = format!("{:.1}s", speed_boost.timer.remaining_secs());
+
+        if hud.is_some() {
+            for (mut text, text_owner) in text_query.iter_mut() {
+                if text_owner.0 == *player {
+                    text.0 = remaining.clone();
+                }
+            }
+            continue;
+        }
+
+        commands.spawn((
+            SpeedBoostHud(*player),
+            hud_node(*player, 50.0),
+            children![
+                (
+                    ImageNode {
+                        image: player_assets.boost_icon.clone(),
+                        ..default()
+                    },
+                    Node {
+                        width: Val::Px(32.0),
+                        height: Val::Px(32.0),
+                        margin: UiRect::right(Val::Px(6.0)),
+                        ..default()
+                    },
+                ),
+                (Text::new(remaining), SpeedBoostHudText(*player)),
+            ],
+        ));
+    }
+}
+
+pub const THROW_CHARGE_COLOR: Color = Color::srgb(1.0, 0.75, 0.3);
+const THROW_FULL_CHARGE_COLOR: Color = Color::srgb(1.0, 0.35, 0.2);
+
+pub fn wool_hud_text(ammo: &WoolAmmo, throw_mode: &ThrowMode) -> String {
+    let arc = if throw_mode.high_arc { "High" } else { "Low" };
+    format!("x {}/{}  {arc} arc", ammo.current, ammo.max)
+}
+
+// Muestra la lana que le queda a cada player (en rojo si no le queda ninguna), el arco
+// elegido y la carga del lanzamiento
+pub fn update_wool_hud(
+    player_query: Query<(&PlayerId, &WoolAmmo, &ThrowMode)>,
+    mut text_query: Query<(&WoolHudText, &mut Text, &mut TextColor)>,
+    mut fill_query: Query<(&ThrowChargeHudFill, &mut Node, &mut BackgroundColor)>,
+) {
+    for (owner, mut text, mut color) in text_query.iter_mut() {
+        let Some((_, ammo, throw_mode)) = player_query
+            .iter()
+            .find(|(player, _, _)| **player == owner.0)
+        else {
+            continue;
+        };
+        text.0 = wool_hud_text(ammo, throw_mode);
+        color.0 = if ammo.current == 0 {
+            WOOL_EMPTY_COLOR
+        } else {
+            Color::WHITE
+        };
+    }
+
+    for (fill, mut node, mut color) in fill_query.iter_mut() {
+        let Some((_, _, throw_mode)) = player_query
+            .iter()
+            .find(|(player, _, _)| **player == fill.0)
+        else {
+            continue;
+        };
+        node.width = Val::Percent(throw_mode.charge * 100.0);
+        color.0 = if throw_mode.charge >= 1.0 {
+            THROW_FULL_CHARGE_COLOR
+        } else {
+            THROW_CHARGE_COLOR
+        };
+    }
+}
+
+// Nodo del HUD de un player: el jugador 1 a la izquierda y el 2 a la derecha
+pub fn hud_node(player: PlayerId, top: f32) -> Node {
+    let mut node = Node {
+        position_type: PositionType::Absolute,
+        top: Val::Px(top),
+        align_items: AlignItems::Center,
+        ..default()
+    };
+    if player.0 == 0 {
+        node.left = Val::Px(14.0);
+    } else {
+        node.right = Val::Px(14.0);
+    }
+    node
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn heart_frames_follow_half_hearts() {
+        // 5 medios corazones en 3 contenedores: lleno, lleno, medio
+        let health = Health { current: 5, max: 6 };
+        assert_eq!(heart_frame(&health, 0), HEART_FULL_FRAME);
+        assert_eq!(heart_frame(&health, 1), HEART_FULL_FRAME);
+        assert_eq!(heart_frame(&health, 2), HEART_HALF_FRAME);
+
+        let health = Health { current: 2, max: 6 };
+        assert_eq!(heart_frame(&health, 0), HEART_FULL_FRAME);
+        assert_eq!(heart_frame(&health, 1), HEART_EMPTY_FRAME);
+        assert_eq!(heart_frame(&health, 2), HEART_EMPTY_FRAME);
+
+        let health = Health { current: 0, max: 6 };
+        assert_eq!(heart_frame(&health, 0), HEART_EMPTY_FRAME);
+    }
+
+    #[test]
+    fn odd_max_health_adds_a_container() {
+        assert_eq!(Health::full(5).hearts(), 3);
+        assert_eq!(Health::full(6).hearts(), 3);
+    }
+}
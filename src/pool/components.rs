@@ -0,0 +1,197 @@
+use std::collections::VecDeque;
+
+use bevy::{platform::collections::HashMap, prelude::*};
+use bevy_rapier2d::prelude::{ColliderDisabled, RigidBodyDisabled};
+
+/// Tipos de entidades que se reciclan en lugar de crearse y destruirse.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PoolKind {
+    WoolBall,
+    EnemyProjectile,
+    HitEffect,
+}
+
+impl PoolKind {
+    /// Máximo de entidades activas a la vez: al pasarlo se recicla la más antigua.
+    pub fn max_active(self) -> usize {
+        match self {
+            PoolKind::WoolBall => 64,
+            PoolKind::EnemyProjectile => 64,
+            PoolKind::HitEffect => 16,
+        }
+    }
+}
+
+/// Marca las entidades del pool que están guardadas: ocultas y sin física.
+#[derive(Component)]
+pub struct Inactive;
+
+#[derive(Default)]
+struct KindPool {
+    free: Vec<Entity>,
+    active: VecDeque<Entity>, // De la más antigua a la más nueva
+}
+
+/// Pool de entidades reutilizables.
+///
+/// Quien crea una entidad la pide con `reuse`; si no hay ninguna libre la crea él y la
+/// registra con `track`. Al acabar con ella la devuelve con `release` en lugar de
+/// destruirla, así que los componentes que no cambian (collider, malla, etc.) se quedan.
+#[derive(Resource, Default)]
+pub struct EntityPool {
+    pools: HashMap<PoolKind, KindPool>,
+}
+
+impl EntityPool {
+    /// Reactiva una entidad libre o, si ya hay demasiadas activas, recicla la más antigua.
+    /// Devuelve `None` si hay que crear una nueva.
+    pub fn reuse(&mut self, commands: &mut Commands, kind: PoolKind) -> Option<Entity> {
+        let pool = self.pools.entry(kind).or_default();
+        let entity = match pool.free.pop() {
+            Some(entity) => entity,
+            None if pool.active.len() >= kind.max_active() => pool.active.pop_front()?,
+            None => return None,
+        };
+        pool.active.push_back(entity);
+        commands
+            .entity(entity)
+            .remove::<(Inactive, ColliderDisabled, RigidBodyDisabled)>()
+            .insert(Visibility::Inherited);
+        Some(entity)
+    }
+
+    /// Registra una entidad recién creada para el pool.
+    pub fn track(&mut self, kind: PoolKind, entity: Entity) {
+        self.pools.entry(kind).or_default().active.push_back(entity);
+    }
+
+    /// Guarda una entidad activa para reutilizarla más tarde.
+    pub fn release(&mut self, commands: &mut Commands, kind: PoolKind, entity: Entity) {
+        let pool = self.pools.entry(kind).or_default();
+        let Some(index) = pool.active.iter().position(|active| *active == entity) else {
+            return; // Ya estaba guardada
+        };
+        pool.active.remove(index);
+        pool.free.push(entity);
+        commands.entity(entity).insert((
+            Inactive,
+            Visibility::Hidden,
+            ColliderDisabled,
+            RigidBodyDisabled,
+        ));
+    }
+
+    /// Guarda todas las entidades activas de un tipo, p. ej. al salir del nivel.
+    pub fn release_all(&mut self, commands: &mut Commands, kind: PoolKind) {
+        let active: Vec<Entity> = self
+            .pools
+            .get(&kind)
+            .map_or_else(Vec::new, |pool| pool.active.iter().copied().collect());
+        for entity in active {
+            self.release(commands, kind, entity);
+        }
+    }
+
+    /// Entidades activas y libres de un tipo.
+    pub fn counts(&self, kind: PoolKind) -> (usize, usize) {
+        self.pools
+            .get(&kind)
+            .map_or((0, 0), |pool| (pool.active.len(), pool.free.len()))
+    }
+}
+
+/// Layouts de atlas que comparten todos los sprites con la misma rejilla, para no
+/// añadir uno nuevo a `Assets` cada vez que se crea un puntero. Los de las hojas de los
+/// personajes están en `CharacterSheets`.
+#[derive(Resource)]
+pub struct SharedAtlasLayouts {
+    pub crosshair: Handle<TextureAtlasLayout>, // Puntero: 5x1 frames de 16 px
+}
+
+/// Destello que aparece donde una bola de lana golpea a un enemigo.
+#[derive(Component)]
+pub struct HitEffect {
+    pub timer: Timer,
+}
+
+/// Prueba de carga (`--stress`): los players lanzan sin parar y cada segundo se muestra
+/// cuántas entidades y layouts de atlas hay, que deben mantenerse estables.
+#[derive(Resource)]
+pub struct StressTest {
+    pub report_timer: Timer,
+    pub shots: u32,
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::world::CommandQueue;
+
+    use super::*;
+
+    #[test]
+    fn released_entities_are_reused() {
+        let mut world = World::new();
+        let ball = world.spawn_empty().id();
+        let mut pool = EntityPool::default();
+        pool.track(PoolKind::WoolBall, ball);
+
+        let mut queue = CommandQueue::default();
+        let mut commands = Commands::new(&mut queue, &world);
+        pool.release(&mut commands, PoolKind::WoolBall, ball);
+        // Soltarla dos veces no la duplica en la lista de libres
+        pool.release(&mut commands, PoolKind::WoolBall, ball);
+        queue.apply(&mut world);
+
+        assert_eq!(pool.counts(PoolKind::WoolBall), (0, 1));
+        assert!(world.entity(ball).contains::<Inactive>());
+        assert!(world.entity(ball).contains::<ColliderDisabled>());
+
+        let mut commands = Commands::new(&mut queue, &world);
+        assert_eq!(pool.reuse(&mut commands, PoolKind::WoolBall), Some(ball));
+        assert_eq!(pool.reuse(&mut commands, PoolKind::WoolBall), None);
+        queue.apply(&mut world);
+
+        assert_eq!(pool.counts(PoolKind::WoolBall), (1, 0));
+        assert!(!world.entity(ball).contains::<Inactive>());
+        assert!(!world.entity(ball).contains::<ColliderDisabled>());
+    }
+
+    #[test]
+    fn oldest_entity_is_recycled_at_max_active() {
+        let mut world = World::new();
+        let kind = PoolKind::HitEffect;
+        let effects: Vec<Entity> = (0..kind.max_active())
+            .map(|_| world.spawn_empty().id())
+            .collect();
+        let mut pool = EntityPool::default();
+        for effect in &effects {
+            pool.track(kind, *effect);
+        }
+
+        let mut queue = CommandQueue::default();
+        let mut commands = Commands::new(&mut queue, &world);
+        assert_eq!(pool.reuse(&mut commands, kind), Some(effects[0]));
+        assert_eq!(pool.reuse(&mut commands, kind), Some(effects[1]));
+        queue.apply(&mut world);
+
+        assert_eq!(pool.counts(kind), (kind.max_active(), 0));
+    }
+
+    #[test]
+    fn release_all_only_touches_one_kind() {
+        let mut world = World::new();
+        let ball = world.spawn_empty().id();
+        let projectile = world.spawn_empty().id();
+        let mut pool = EntityPool::default();
+        pool.track(PoolKind::WoolBall, ball);
+        pool.track(PoolKind::EnemyProjectile, projectile);
+
+        let mut queue = CommandQueue::default();
+        let mut commands = Commands::new(&mut queue, &world);
+        pool.release_all(&mut commands, PoolKind::EnemyProjectile);
+        queue.apply(&mut world);
+
+        assert_eq!(pool.counts(PoolKind::EnemyProjectile), (0, 1));
+        assert_eq!(pool.counts(PoolKind::WoolBall), (1, 0));
+    }
+}
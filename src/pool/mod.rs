@@ -0,0 +1,55 @@
+pub mod components;
+pub mod systems;
+
+use std::env;
+
+use bevy::prelude::*;
+
+use crate::{
+    cursor::systems::spawn_projectile_on_click,
+    game_state::{GameState, is_pause_transition},
+    input::systems::update_action_state,
+    pool::{
+        components::{EntityPool, StressTest},
+        systems::*,
+    },
+};
+
+const STRESS_ARG: &str = "--stress";
+const STRESS_REPORT_INTERVAL: f32 = 1.0;
+
+pub struct PoolPlugin;
+
+impl Plugin for PoolPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<EntityPool>()
+            .add_systems(Startup, init_shared_atlas_layouts)
+            .add_systems(
+                Update,
+                animate_hit_effects.run_if(in_state(GameState::Game)),
+            )
+            .add_systems(
+                OnExit(GameState::Game),
+                release_hit_effects.run_if(not(is_pause_transition)),
+            );
+
+        if env::args().skip(1).any(|arg| arg == STRESS_ARG) {
+            app.insert_resource(StressTest {
+                report_timer: Timer::from_seconds(STRESS_REPORT_INTERVAL, TimerMode::Repeating),
+                shots: 0,
+            })
+            .add_systems(
+                PreUpdate,
+                stress_fire_system
+                    .after(update_action_state)
+                    .run_if(in_state(GameState::Game)),
+            )
+            .add_systems(
+                Update,
+                stress_report_system
+                    .after(spawn_projectile_on_click)
+                    .run_if(in_state(GameState::Game)),
+            );
+        }
+    }
+}
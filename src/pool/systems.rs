@@ -0,0 +1,143 @@
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::{
+    cursor::components::{AimDevice, AimState, Crosshair},
+    input::components::{Action, PlayerActions},
+    player::components::{PlayerId, WoolAmmo},
+    pool::components::{EntityPool, HitEffect, Inactive, PoolKind, SharedAtlasLayouts, StressTest},
+};
+
+const HIT_EFFECT_TIME: f32 = 0.25;
+const HIT_EFFECT_SIZE: f32 = 48.0;
+// Ángulos de lanzamiento de la prueba de carga, en radianes sobre la horizontal
+const STRESS_MIN_ELEVATION: f32 = 0.2;
+const STRESS_MAX_ELEVATION: f32 = 1.2;
+
+pub fn init_shared_atlas_layouts(
+    mut commands: Commands,
+    mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+) {
+    commands.insert_resource(SharedAtlasLayouts {
+        crosshair: texture_atlas_layouts.add(TextureAtlasLayout::from_grid(
+            UVec2::splat(16),
+            5,
+            1,
+            None,
+            None,
+        )),
+    });
+}
+
+// Muestra un destello con la imagen del proyectil, reutilizando uno del pool si hay
+pub fn spawn_hit_effect(
+    commands: &mut Commands,
+    pool: &mut EntityPool,
+    position: Vec2,
+    image: Handle<Image>,
+) {
+    let entity = pool
+        .reuse(commands, PoolKind::HitEffect)
+        .unwrap_or_else(|| {
+            let entity = commands.spawn_empty().id();
+            pool.track(PoolKind::HitEffect, entity);
+            entity
+        });
+    commands.entity(entity).insert((
+        Sprite {
+            image,
+            custom_size: Some(Vec2::splat(HIT_EFFECT_SIZE)),
+            ..default()
+        },
+        Transform::from_translation(position.extend(99.0)),
+        HitEffect {
+            timer: Timer::from_seconds(HIT_EFFECT_TIME, TimerMode::Once),
+        },
+    ));
+}
+
+// El destello crece y se desvanece; al terminar vuelve al pool
+pub fn animate_hit_effects(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut pool: ResMut<EntityPool>,
+    mut effect_query: Query<
+        (Entity, &mut HitEffect, &mut Transform, &mut Sprite),
+        Without<Inactive>,
+    >,
+) {
+    for (entity, mut effect, mut transform, mut sprite) in effect_query.iter_mut() {
+        effect.timer.tick(time.delta());
+        if effect.timer.finished() {
+            pool.release(&mut commands, PoolKind::HitEffect, entity);
+            continue;
+        }
+        let progress = effect.timer.fraction();
+        transform.scale = Vec3::splat(0.3 + 0.7 * progress);
+        sprite.color = Color::WHITE.with_alpha(1.0 - progress);
+    }
+}
+
+pub fn release_hit_effects(mut commands: Commands, mut pool: ResMut<EntityPool>) {
+    pool.release_all(&mut commands, PoolKind::HitEffect);
+}
+
+// Prueba de carga: munición infinita, sin enfriamiento y un lanzamiento cada dos frames
+// (un frame se pulsa y el siguiente se suelta) con ángulos al azar
+pub fn stress_fire_system(
+    mut stress: ResMut<StressTest>,
+    mut player_actions: ResMut<PlayerActions>,
+    mut player_query: Query<(&PlayerId, &mut WoolAmmo)>,
+    mut crosshair_query: Query<&mut AimState, With<Crosshair>>,
+    mut press: Local<bool>,
+) {
+    *press = !*press;
+    let mut rng = rand::thread_rng();
+
+    for (player, mut ammo) in player_query.iter_mut() {
+        ammo.current = ammo.max;
+        let cooldown = ammo.cooldown.duration();
+        ammo.cooldown.tick(cooldown);
+        if *press {
+            player_actions.get_mut(player.0).update(Action::Throw, true);
+            stress.shots += 1;
+        }
+    }
+
+    if *press {
+        for mut aim_state in crosshair_query.iter_mut() {
+            aim_state.device = AimDevice::Keyboard;
+            aim_state.elevation = rng.gen_range(STRESS_MIN_ELEVATION..STRESS_MAX_ELEVATION);
+        }
+    }
+}
+
+// Cada segundo muestra las entidades, los layouts de atlas y el estado de los pools
+pub fn stress_report_system(
+    time: Res<Time>,
+    mut stress: ResMut<StressTest>,
+    pool: Res<EntityPool>,
+    texture_atlas_layouts: Res<Assets<TextureAtlasLayout>>,
+    entities: Query<()>,
+) {
+    stress.report_timer.tick(time.delta());
+    if !stress.report_timer.just_finished() {
+        return;
+    }
+    let (balls_active, balls_free) = pool.counts(PoolKind::WoolBall);
+    let (projectiles_active, projectiles_free) = pool.counts(PoolKind::EnemyProjectile);
+    let (effects_active, effects_free) = pool.counts(PoolKind::HitEffect);
+    info!(
+        "[stress] t={:.0}s shots={} entities={} atlas_layouts={} wool_balls={}+{} enemy_projectiles={}+{} hit_effects={}+{}",
+        time.elapsed_secs(),
+        stress.shots,
+        entities.iter().count(),
+        texture_atlas_layouts.len(),
+        balls_active,
+        balls_free,
+        projectiles_active,
+        projectiles_free,
+        effects_active,
+        effects_free,
+    );
+}
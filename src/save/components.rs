@@ -0,0 +1,111 @@
+use std::{collections::HashMap, path::PathBuf};
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::game_state::Level;
+
+// Ruta del fichero de guardado (por defecto o pasada por línea de comandos)
+#[derive(Resource, Debug, Clone)]
+pub struct SavePath(pub PathBuf);
+
+/// Mejores marcas de un nivel.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct LevelRecord {
+    pub best_score: u32,
+    pub best_time: Option<f32>, // Segundos
+    pub completed: bool,
+    #[serde(default)]
+    pub best_splits: Vec<Option<f32>>, // Tiempos por checkpoint de la mejor marca
+}
+
+impl LevelRecord {
+    /// Apunta una partida terminada y devuelve si ha mejorado la puntuación y el tiempo.
+    /// La primera vez que se completa el nivel cualquier puntuación cuenta como récord.
+    pub fn record_completion(
+        &mut self,
+        score: u32,
+        time: f32,
+        splits: &[Option<f32>],
+    ) -> (bool, bool) {
+        let new_best_score = !self.completed || score > self.best_score;
+        let new_best_time = self.best_time.is_none_or(|best_time| time < best_time);
+
+        if new_best_score {
+            self.best_score = score;
+        }
+        if new_best_time {
+            self.best_time = Some(time);
+            self.best_splits = splits.to_vec();
+        }
+        self.completed = true;
+
+        (new_best_score, new_best_time)
+    }
+}
+
+/// Contenido del fichero de guardado.
+#[derive(Resource, Debug, Default, Serialize, Deserialize)]
+pub struct SaveData {
+    pub levels: HashMap<Level, LevelRecord>,
+}
+
+impl SaveData {
+    pub fn record(&self, level: Level) -> Option<&LevelRecord> {
+        self.levels.get(&level)
+    }
+}
+
+/// Puntuación de la partida en curso.
+#[derive(Resource, Debug, Default)]
+pub struct RunStats {
+    pub score: u32,
+    pub new_best_score: bool,
+    pub new_best_time: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_completion_is_always_a_record() {
+        let mut record = LevelRecord::default();
+
+        assert_eq!(
+            record.record_completion(0, 42.0, &[Some(10.0)]),
+            (true, true)
+        );
+        assert!(record.completed);
+        assert_eq!(record.best_score, 0);
+        assert_eq!(record.best_time, Some(42.0));
+        assert_eq!(record.best_splits, vec![Some(10.0)]);
+    }
+
+    #[test]
+    fn keeps_best_score_and_best_time_independently() {
+        let mut record = LevelRecord::default();
+        record.record_completion(100, 40.0, &[Some(10.0)]);
+
+        // Más puntos pero más lento: solo cambia la puntuación
+        assert_eq!(
+            record.record_completion(150, 50.0, &[Some(20.0)]),
+            (true, false)
+        );
+        assert_eq!(record.best_score, 150);
+        assert_eq!(record.best_time, Some(40.0));
+        assert_eq!(record.best_splits, vec![Some(10.0)]);
+
+        // Menos puntos pero más rápido: solo cambia el tiempo, con sus splits
+        assert_eq!(
+            record.record_completion(50, 30.0, &[Some(5.0)]),
+            (false, true)
+        );
+        assert_eq!(record.best_score, 150);
+        assert_eq!(record.best_time, Some(30.0));
+        assert_eq!(record.best_splits, vec![Some(5.0)]);
+
+        // Empatar no es récord
+        assert_eq!(record.record_completion(150, 30.0, &[]), (false, false));
+    }
+}
@@ -0,0 +1,75 @@
+pub mod components;
+pub mod systems;
+
+use std::{env, path::PathBuf};
+
+use bevy::prelude::*;
+
+use crate::{
+    game_state::{GameState, LevelState},
+    save::{
+        components::{RunStats, SavePath},
+        systems::*,
+    },
+    speedrun::systems::stop_level_timer,
+};
+
+const SAVE_FILE_NAME: &str = "save.json";
+const SAVE_ARG: &str = "--save";
+
+pub struct SavePlugin;
+
+impl Plugin for SavePlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(SavePath(save_file_path()))
+            .init_resource::<RunStats>()
+            .add_systems(Startup, load_save_data)
+            .add_systems(OnEnter(LevelState::Loading), reset_run_stats)
+            .add_systems(
+                OnEnter(GameState::LevelComplete),
+                record_level_completion.after(stop_level_timer),
+            );
+    }
+}
+
+/// Ruta del fichero de guardado: `--save <ruta>` o el directorio de datos de la plataforma.
+pub fn save_file_path() -> PathBuf {
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == SAVE_ARG {
+            if let Some(path) = args.next() {
+                return PathBuf::from(path);
+            }
+        } else if let Some(path) = arg.strip_prefix("--save=") {
+            return PathBuf::from(path);
+        }
+    }
+
+    data_dir().join(SAVE_FILE_NAME)
+}
+
+/// Directorio de datos del juego según la plataforma.
+pub fn data_dir() -> PathBuf {
+    let base = if cfg!(target_os = "windows") {
+        env::var_os("APPDATA").map(PathBuf::from)
+    } else if cfg!(target_os = "macos") {
+        env::var_os("HOME").map(|home| PathBuf::from(home).join("Library/Application Support"))
+    } else {
+        env::var_os("XDG_DATA_HOME")
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/share")))
+    };
+
+    base.unwrap_or_else(|| PathBuf::from(".")).join("the5cats")
+}
+
+/// Formatea segundos como `mm:ss.cc`.
+pub fn format_time(seconds: f32) -> String {
+    let centis = (seconds * 100.0).round() as u32;
+    format!(
+        "{:02}:{:02}.{:02}",
+        centis / 6000,
+        (centis / 100) % 60,
+        centis % 100
+    )
+}
@@ -0,0 +1,61 @@
+use std::fs;
+
+use bevy::prelude::*;
+
+use crate::{
+    map::components::CurrentLevelInfo,
+    save::components::{RunStats, SaveData, SavePath},
+    speedrun::components::{LevelTimer, RunSplits},
+};
+
+// Carga el fichero de guardado, o empieza uno vacío si no existe
+pub fn load_save_data(mut commands: Commands, save_path: Res<SavePath>) {
+    let save_data = match fs::read_to_string(&save_path.0) {
+        Ok(json) => serde_json::from_str(&json).unwrap_or_else(|err| {
+            warn!("Failed to parse save file {:?}: {err}", save_path.0);
+            SaveData::default()
+        }),
+        Err(_) => SaveData::default(),
+    };
+
+    commands.insert_resource(save_data);
+}
+
+pub fn write_save_data(save_path: &SavePath, save_data: &SaveData) {
+    if let Some(parent) = save_path.0.parent()
+        && let Err(err) = fs::create_dir_all(parent)
+    {
+        warn!("Failed to create save directory {:?}: {err}", parent);
+        return;
+    }
+
+    match serde_json::to_string_pretty(save_data) {
+        Ok(json) => {
+            if let Err(err) = fs::write(&save_path.0, json) {
+                warn!("Failed to write save file {:?}: {err}", save_path.0);
+            }
+        }
+        Err(err) => warn!("Failed to serialize save data: {err}"),
+    }
+}
+
+pub fn reset_run_stats(mut run_stats: ResMut<RunStats>) {
+    *run_stats = RunStats::default();
+}
+
+// Actualiza las mejores marcas del nivel al terminarlo y las guarda en disco
+pub fn record_level_completion(
+    level_info: Res<CurrentLevelInfo>,
+    save_path: Res<SavePath>,
+    mut save_data: ResMut<SaveData>,
+    mut run_stats: ResMut<RunStats>,
+    level_timer: Res<LevelTimer>,
+    run_splits: Res<RunSplits>,
+) {
+    let record = save_data.levels.entry(level_info.level).or_default();
+
+    (run_stats.new_best_score, run_stats.new_best_time) =
+        record.record_completion(run_stats.score, level_timer.elapsed, &run_splits.0);
+
+    write_save_data(&save_path, &save_data);
+}
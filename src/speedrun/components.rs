@@ -0,0 +1,29 @@
+use bevy::prelude::*;
+
+/// Cronómetro del nivel. Se arma al cargar el nivel y arranca con el primer movimiento del player.
+#[derive(Resource, Debug, Default)]
+pub struct LevelTimer {
+    pub elapsed: f32,
+    pub running: bool,
+    pub finished: bool,
+}
+
+/// Tiempos de paso por cada checkpoint (por índice) en la partida actual.
+#[derive(Resource, Debug, Default)]
+pub struct RunSplits(pub Vec<Option<f32>>);
+
+/// Overlay opcional con los splits frente a la mejor marca.
+#[derive(Resource, Debug, Default)]
+pub struct SplitsOverlay {
+    pub visible: bool,
+}
+
+// Marcadores del HUD del cronómetro
+#[derive(Component)]
+pub struct TimerHud;
+
+#[derive(Component)]
+pub struct TimerHudText;
+
+#[derive(Component)]
+pub struct SplitsHud;
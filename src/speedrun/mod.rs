@@ -0,0 +1,51 @@
+pub mod components;
+pub mod systems;
+
+use bevy::prelude::*;
+
+use crate::{
+    game_state::{GameState, LevelState, is_pause_transition},
+    speedrun::{
+        components::{LevelTimer, RunSplits, SplitsOverlay, TimerHud},
+        systems::*,
+    },
+};
+
+pub struct SpeedrunPlugin;
+
+impl Plugin for SpeedrunPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LevelTimer>()
+            .init_resource::<RunSplits>()
+            .init_resource::<SplitsOverlay>()
+            .add_systems(OnEnter(LevelState::LevelLoaded), reset_level_timer)
+            .add_systems(
+                OnEnter(GameState::Game),
+                spawn_timer_hud.run_if(not(is_pause_transition)),
+            )
+            .add_systems(
+                Update,
+                (
+                    start_level_timer,
+                    tick_level_timer.after(start_level_timer),
+                    record_splits.after(tick_level_timer),
+                    toggle_splits_overlay,
+                    update_timer_hud.after(tick_level_timer),
+                    update_splits_overlay.after(record_splits),
+                )
+                    .run_if(in_state(GameState::Game))
+                    .run_if(in_state(LevelState::LevelLoaded)),
+            )
+            .add_systems(OnEnter(GameState::LevelComplete), stop_level_timer)
+            .add_systems(
+                OnExit(GameState::Game),
+                despawn_timer_hud.run_if(not(is_pause_transition)),
+            );
+    }
+}
+
+fn despawn_timer_hud(mut commands: Commands, query: Query<Entity, With<TimerHud>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn();
+    }
+}
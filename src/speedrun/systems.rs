@@ -0,0 +1,185 @@
+use bevy::prelude::*;
+
+use crate::{
+    checkpoints::components::{Checkpoint, CheckpointReached},
+    input::components::{Action, ActionState},
+    map::components::CurrentLevelInfo,
+    physics::Velocity,
+    player::components::PlayerCharacter,
+    save::{components::SaveData, format_time},
+    speedrun::components::{
+        LevelTimer, RunSplits, SplitsHud, SplitsOverlay, TimerHud, TimerHudText,
+    },
+};
+
+const TIMER_COLOR: Color = Color::srgb(0.9, 0.9, 0.9);
+const AHEAD_COLOR: Color = Color::srgb(0.3, 0.9, 0.3);
+const BEHIND_COLOR: Color = Color::srgb(0.9, 0.3, 0.3);
+
+pub fn reset_level_timer(mut level_timer: ResMut<LevelTimer>, mut run_splits: ResMut<RunSplits>) {
+    *level_timer = LevelTimer::default();
+    run_splits.0.clear();
+}
+
+// Arranca el cronómetro la primera vez que el player se mueve
+pub fn start_level_timer(
+    mut level_timer: ResMut<LevelTimer>,
+    player_query: Query<&Velocity, With<PlayerCharacter>>,
+) {
+    if level_timer.running || level_timer.finished {
+        return;
+    }
+
+    // La caída inicial por gravedad no cuenta como movimiento
+    if player_query
+        .iter()
+        .any(|velocity| velocity.velocity.x != 0.0 || velocity.velocity.y > 0.0)
+    {
+        level_timer.running = true;
+    }
+}
+
+pub fn tick_level_timer(time: Res<Time>, mut level_timer: ResMut<LevelTimer>) {
+    if level_timer.running {
+        level_timer.elapsed += time.delta_secs();
+    }
+}
+
+pub fn stop_level_timer(mut level_timer: ResMut<LevelTimer>) {
+    level_timer.running = false;
+    level_timer.finished = true;
+}
+
+pub fn record_splits(
+    mut checkpoint_events: EventReader<CheckpointReached>,
+    level_timer: Res<LevelTimer>,
+    mut run_splits: ResMut<RunSplits>,
+) {
+    for event in checkpoint_events.read() {
+        if run_splits.0.len() <= event.index {
+            run_splits.0.resize(event.index + 1, None);
+        }
+        run_splits.0[event.index] = Some(level_timer.elapsed);
+    }
+}
+
+pub fn toggle_splits_overlay(actions: Res<ActionState>, mut splits_overlay: ResMut<SplitsOverlay>) {
+    if actions.just_pressed(Action::ToggleSplits) {
+        splits_overlay.visible = !splits_overlay.visible;
+    }
+}
+
+// Cronómetro arriba a la derecha, con la lista de splits debajo
+pub fn spawn_timer_hud(mut commands: Commands, level_timer: Res<LevelTimer>) {
+    commands.spawn((
+        TimerHud,
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(7.0),
+            right: Val::Px(14.0),
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::FlexEnd,
+            ..default()
+        },
+        children![
+            (
+                Text::new(format_time(level_timer.elapsed)),
+                TextFont {
+                    font_size: 28.0,
+                    ..default()
+                },
+                TextColor(TIMER_COLOR),
+                TimerHudText,
+            ),
+            (
+                SplitsHud,
+                Node {
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::FlexEnd,
+                    display: Display::None,
+                    ..default()
+                },
+            )
+        ],
+    ));
+}
+
+pub fn update_timer_hud(
+    level_timer: Res<LevelTimer>,
+    mut text_query: Query<&mut Text, With<TimerHudText>>,
+) {
+    for mut text in text_query.iter_mut() {
+        text.0 = format_time(level_timer.elapsed);
+    }
+}
+
+// Reconstruye el overlay de splits cuando cambia algún split o su visibilidad
+pub fn update_splits_overlay(
+    mut commands: Commands,
+    splits_overlay: Res<SplitsOverlay>,
+    run_splits: Res<RunSplits>,
+    level_info: Res<CurrentLevelInfo>,
+    save_data: Res<SaveData>,
+    checkpoint_query: Query<&Checkpoint>,
+    mut hud_query: Query<(Entity, &mut Node), With<SplitsHud>>,
+) {
+    if !splits_overlay.is_changed() && !run_splits.is_changed() {
+        return;
+    }
+
+    let Ok((hud_entity, mut node)) = hud_query.single_mut() else {
+        return;
+    };
+
+    node.display = if splits_overlay.visible {
+        Display::Flex
+    } else {
+        Display::None
+    };
+    if !splits_overlay.visible {
+        return;
+    }
+
+    let record = save_data.record(level_info.level);
+    let checkpoint_count = checkpoint_query.iter().count();
+
+    commands
+        .entity(hud_entity)
+        .despawn_related::<Children>()
+        .with_children(|parent| {
+            for index in 0..checkpoint_count {
+                let current = run_splits.0.get(index).copied().flatten();
+                let best = record
+                    .and_then(|record| record.best_splits.get(index).copied())
+                    .flatten();
+                let (line, color) = split_line(&format!("CP{}", index + 1), current, best);
+                parent.spawn((Text::new(line), TextColor(color)));
+            }
+
+            // El nivel termina al salir de Game, así que aquí solo se muestra la mejor marca
+            let best = record.and_then(|record| record.best_time);
+            let (line, color) = split_line("End", None, best);
+            parent.spawn((Text::new(line), TextColor(color)));
+        });
+}
+
+// Texto y color de una fila del overlay: tiempo actual y diferencia con la mejor marca
+fn split_line(label: &str, current: Option<f32>, best: Option<f32>) -> (String, Color) {
+    match (current, best) {
+        (Some(current), Some(best)) => {
+            let delta = current - best;
+            let color = if delta <= 0.0 {
+                AHEAD_COLOR
+            } else {
+                BEHIND_COLOR
+            };
+            (
+                format!("{label}  {}  {delta:+.2}", format_time(current)),
+                color,
+            )
+        }
+        (Some(current), None) => (format!("{label}  {}", format_time(current)), TIMER_COLOR),
+        (None, Some(best)) => (format!("{label}  PB {}", format_time(best)), TIMER_COLOR),
+        (None, None) => (format!("{label}  --:--.--"), TIMER_COLOR),
+    }
+}